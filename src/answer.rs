@@ -0,0 +1,91 @@
+//! Checking typed Vietnamese sentences.
+
+use crate::grammar::{
+    dialect::Dialect,
+    segmenter::{self, Segment},
+    syllable,
+    word::Word,
+};
+
+/// Whether the answer is the expected sentence.
+///
+/// The answer is split into the expected words, so the syllables of compounds can be separated
+/// with spaces or hyphens, and every word must be an accepted form of the expected word.
+pub fn check_sentence(expected: &[Word], answer: &str, dialect: Option<Dialect>) -> bool {
+    let segments = segmenter::segment(answer, expected);
+
+    segments.len() == expected.len()
+        && segments.iter().zip(expected).all(|(segment, word)| {
+            matches!(segment, Segment::Known(..)) && word.accepts(segment.text(), dialect)
+        })
+}
+
+/// Whether the answer is the expected sentence when the tone marks are ignored.
+///
+/// **Hoc sinh doc sach**: học sinh đọc sách.
+pub fn check_sentence_without_tones(
+    expected: &[Word],
+    answer: &str,
+    dialect: Option<Dialect>,
+) -> bool {
+    let answer = syllable::strip_tones(&syllable::normalize(answer));
+
+    // Every combination of the accepted forms of the words
+    expected
+        .iter()
+        .fold(vec![String::new()], |sentences, word| {
+            sentences
+                .iter()
+                .flat_map(|sentence| {
+                    word.accepted_forms(dialect)
+                        .into_iter()
+                        .map(move |form| format!("{} {}", sentence, form))
+                })
+                .collect()
+        })
+        .iter()
+        .any(|sentence| syllable::strip_tones(&syllable::normalize(sentence)) == answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Result};
+
+    fn words(contents: &[&str]) -> Result<Vec<Word>> {
+        contents
+            .iter()
+            .map(|content| {
+                Word::defaults()
+                    .find(|word| word.to_string() == *content)
+                    .ok_or_else(|| anyhow!("Word {} not found", content))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_sentence() -> Result<()> {
+        let expected = words(&["học sinh", "đọc", "sách"])?;
+
+        assert!(check_sentence(&expected, "Học sinh đọc sách.", None));
+        assert!(check_sentence(&expected, "học-sinh đọc sách", None));
+        assert!(!check_sentence(&expected, "học sinh đọc", None));
+        assert!(!check_sentence(&expected, "sách đọc học sinh", None));
+
+        let expected = words(&["học sinh", "cao"])?;
+        assert!(!check_sentence(&expected, "học sinh cao sách", None));
+
+        assert!(check_sentence_without_tones(
+            &expected,
+            "hoc sinh cao",
+            None
+        ));
+        assert!(!check_sentence_without_tones(&expected, "hoc sinh", None));
+
+        let expected = words(&["bát"])?;
+        assert!(check_sentence(&expected, "chén", None));
+        assert!(!check_sentence(&expected, "chén", Some(Dialect::Northern)));
+
+        Ok(())
+    }
+}
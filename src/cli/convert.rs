@@ -0,0 +1,130 @@
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use std::{fs, path::Path};
+use tieng_viet::grammar::{
+    defaults_parser, lexicon,
+    structured::{self, Entry, Lexicon},
+};
+
+/// The formats of the lexicon.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum LexiconFormat {
+    /// Word lines, a list for every class after a comment with its name.
+    Lines,
+    Toml,
+    Json,
+}
+
+/// Print the words of a file in another format.
+pub fn run(path: &Path, to: LexiconFormat) -> Result<()> {
+    let data = fs::read_to_string(path).with_context(|| format!("Could not read {:?}", path))?;
+    let lexicon = read(path, &data)?;
+
+    match to {
+        LexiconFormat::Lines => print!("{}", to_lines(&lexicon)?),
+        LexiconFormat::Toml => print!("{}", lexicon.to_toml()?),
+        LexiconFormat::Json => print!("{}", lexicon.to_json()?),
+    }
+
+    Ok(())
+}
+
+/// Read a word list like `verbs.txt` or a file in the structured format.
+fn read(path: &Path, data: &str) -> Result<Lexicon> {
+    if structured::is_structured(path) {
+        return structured::parse(path, data);
+    }
+
+    let list = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("{:?} is not a word list", path))?;
+    let words = defaults_parser::parse_lines(data)
+        .map(|(number, line)| {
+            Entry::from_line(list, line).with_context(|| format!("{}:{}", list, number))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Lexicon {
+        schema: None,
+        words,
+    })
+}
+
+/// The words as lines, grouped by their word lists.
+///
+/// The examples can't be written as a line, they're left out with a warning.
+fn to_lines(lexicon: &Lexicon) -> Result<String> {
+    let mut text = String::new();
+    for (list, _) in lexicon::FILES.iter() {
+        let mut lines = vec![];
+        for entry in &lexicon.words {
+            if entry.list()? != *list {
+                continue;
+            }
+            if !entry.examples.is_empty() {
+                eprintln!("The examples of \"{}\" are left out", entry.word);
+            }
+            lines.push(
+                entry
+                    .to_line()
+                    .with_context(|| format!("Could not convert \"{}\"", entry.word))?,
+            );
+        }
+
+        if !lines.is_empty() {
+            text.push_str(&format!("# {}\n{}\n", list, lines.join("\n")));
+        }
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_convert() -> Result<()> {
+        let lines = "đọc(PAST = read + SINO = 讀): read\nxem: watch, see\n";
+        let lexicon = read(Path::new("data/verbs.txt"), lines)?;
+        assert_eq!(lexicon.words.len(), 2);
+
+        // Converting back gives the same lines
+        let toml = lexicon.to_toml()?;
+        assert_eq!(
+            to_lines(&read(Path::new("verbs.toml"), &toml)?)?,
+            format!("# verbs.txt\n{}", lines)
+        );
+
+        assert!(read(Path::new("words.txt"), lines).is_err());
+
+        // The other meanings are kept in the lines
+        let toml = indoc! {r#"
+            [[words]]
+            word = "bát"
+            class = "common noun"
+            meanings = ["bowl", "cup"]
+
+            [words.properties]
+            CLASSIFIER = "cái"
+            OBJECT = true
+        "#};
+        let lexicon = read(Path::new("words.toml"), toml)?;
+        let lines = to_lines(&lexicon)?;
+        assert_eq!(
+            lines,
+            "# common_nouns.txt\nbát(CLASSIFIER = cái + OBJECT + MEANING = cup): bowl\n"
+        );
+        let converted = read(Path::new("common_nouns.txt"), &lines)?;
+        assert_eq!(converted, lexicon);
+        assert_eq!(
+            converted.words()?[0].meanings().collect::<Vec<_>>(),
+            vec!["bowl", "cup"]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{bail, Result};
+use clap::{Args, ValueEnum};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+use std::io::stdout;
+use tieng_viet::{
+    export::{self, Card, Worksheet},
+    grammar::{record, word::Class},
+    settings::Settings,
+    Word,
+};
+
+/// How many sentences are generated when no count is passed.
+const SENTENCES: usize = 20;
+
+/// What is exported and how.
+#[derive(Debug, Args)]
+pub struct Options {
+    /// The words of the lexicon or generated sentences.
+    #[arg(value_enum)]
+    source: Source,
+    #[arg(long, value_enum, default_value_t = Format::Anki)]
+    format: Format,
+    /// Only the words of a class like "noun" or "verb".
+    #[arg(long)]
+    class: Option<Class>,
+    /// How many words or sentences, all words and 20 sentences by default.
+    #[arg(short = 'n', long)]
+    count: Option<usize>,
+    /// Pick the same words or generate the same sentences every time with this seed.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Only sentences formed with a structure, like "CLASSIFIER + HEAD".
+    #[arg(long)]
+    structure: Option<String>,
+    /// The Anki deck the cards are added to, or the title of the worksheet.
+    #[arg(long, default_value = "Tiếng Việt")]
+    title: String,
+}
+
+/// What is exported.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum Source {
+    /// The words of the lexicon with their meanings.
+    Words,
+    /// Generated sentences with their translations.
+    Sentences,
+}
+
+/// How it's exported.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum Format {
+    /// CSV that Anki imports with "File > Import", tagged with the classes of the words.
+    Anki,
+    /// A worksheet with the answers at the end.
+    Markdown,
+    /// A worksheet with the answers on their own page when it's printed.
+    Html,
+    /// A worksheet with the answers on their own page.
+    Latex,
+}
+
+/// Print the cards or the worksheet.
+pub fn run(options: Options, settings: &Settings) -> Result<()> {
+    let seed = options.seed.unwrap_or_else(rand::random);
+
+    let (cards, worksheet) = match options.source {
+        Source::Words => {
+            if options.structure.is_some() {
+                bail!("Only sentences are formed with a structure");
+            }
+
+            let words = Word::defaults().filter(|word| {
+                options
+                    .class
+                    .as_ref()
+                    .is_none_or(|class| class == word.class())
+            });
+            let words = match options.count {
+                Some(count) => words.choose_multiple(&mut StdRng::seed_from_u64(seed), count),
+                None => words.collect(),
+            };
+
+            (
+                words
+                    .iter()
+                    .map(|word| Card::word(word, settings.dialect))
+                    .collect::<Result<Vec<_>>>()?,
+                Worksheet::words(&options.title, &words, settings.dialect)?,
+            )
+        }
+        Source::Sentences => {
+            if options.class.is_some() {
+                bail!("Only words can be exported by their class");
+            }
+
+            let records = record::generate(
+                seed,
+                options.count.unwrap_or(SENTENCES),
+                options.structure.as_deref(),
+                settings,
+            )?;
+
+            (
+                records.iter().map(Card::sentence).collect(),
+                Worksheet::sentences(&options.title, &records),
+            )
+        }
+    };
+
+    let stdout = stdout();
+    let stdout = stdout.lock();
+    match options.format {
+        Format::Anki => export::write_anki_csv(stdout, &cards, &options.title),
+        Format::Markdown => worksheet.write_markdown(stdout),
+        Format::Html => worksheet.write_html(stdout),
+        Format::Latex => worksheet.write_latex(stdout),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::cli::Format;
+use anyhow::Result;
+use std::io::{stdout, Write};
+use tieng_viet::{
+    grammar::record::{self, Record},
+    settings::Settings,
+};
+
+/// Print generated sentences.
+///
+/// Without a seed a random one is used, it's printed with the metadata.
+pub fn run(
+    count: usize,
+    seed: Option<u64>,
+    structure: Option<&str>,
+    metadata: bool,
+    format: Format,
+    settings: &Settings,
+) -> Result<()> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let records = record::generate(seed, count, structure, settings)?;
+
+    let stdout = stdout();
+    let mut stdout = stdout.lock();
+    match format {
+        Format::Text => {
+            for record in &records {
+                writeln!(stdout, "{}", record.sentence)?;
+                if metadata {
+                    write_metadata(&mut stdout, record)?;
+                }
+            }
+        }
+        Format::Tsv => {
+            for record in &records {
+                write!(stdout, "{}\t{}", record.sentence, record.translation)?;
+                if metadata {
+                    write!(stdout, "\t{}", record.structures.join(", "))?;
+                }
+                writeln!(stdout)?;
+            }
+        }
+        Format::JsonLines => record::write_json_lines(&mut stdout, &records)?,
+        Format::Csv => record::write_csv(&mut stdout, &records)?,
+    }
+
+    Ok(())
+}
+
+/// Print the words with their pronunciation, translation and structures below the sentence.
+fn write_metadata<W>(writer: &mut W, record: &Record) -> Result<()>
+where
+    W: Write,
+{
+    for (word, pronunciation) in record.words.iter().zip(&record.pronunciations) {
+        writeln!(
+            writer,
+            "  {} [{}]: {} ({})",
+            word,
+            pronunciation,
+            word.meaning(),
+            word.class()
+        )?;
+    }
+    writeln!(writer, "  Translation: {}", record.translation)?;
+    writeln!(writer, "  Structures: {}", record.structures.join(", "))?;
+    writeln!(writer, "  Seed: {}", record.seed)?;
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use std::{
+    fs,
+    io::{stdin, stdout, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
+use tieng_viet::{
+    grammar::{lexicon, word::Class},
+    import::{self, Columns, Entry, NounRoles, Skipped, Table},
+    profile::Store,
+};
+
+/// The classes that can be picked when it's not known.
+const CLASSES: [&str; 7] = [
+    "common noun",
+    "proper noun",
+    "verb",
+    "adjective",
+    "classifier",
+    "demonstrative",
+    "interjection",
+];
+
+/// Where the words are read from and how they're mapped.
+#[derive(Debug, Args)]
+pub struct Options {
+    /// A CSV or TSV word list, or an Anki deck exported as `.apkg`.
+    file: PathBuf,
+    /// The column or field with the Vietnamese word, a number starting at 1 or its name.
+    #[arg(long, default_value = "1")]
+    word: String,
+    /// The column or field with the English meaning.
+    #[arg(long, default_value = "2")]
+    meaning: String,
+    /// The column or field with the class like "noun" or "verb", otherwise it's taken from the
+    /// Anki tags or inferred from the meaning.
+    #[arg(long)]
+    class_column: Option<String>,
+    /// The class of the words of which it's not known, otherwise it's asked.
+    #[arg(long)]
+    class: Option<Class>,
+    /// Whether the imported nouns can be the subject or object of sentences, like "SUBJECT",
+    /// "SUBJECT+OBJECT" or "none".
+    #[arg(long, default_value = "SUBJECT+OBJECT")]
+    noun_roles: NounRoles,
+    /// The first row of a word list contains the names of the columns.
+    #[arg(long)]
+    header: bool,
+    /// Separates the columns, a tab for `.tsv` files and a comma otherwise.
+    #[arg(long)]
+    delimiter: Option<char>,
+    /// Add the words to the word lists in this directory instead of the lexicon of the user.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Print the word lines instead of adding them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Import the words and report the rows that could not be imported.
+pub fn run(options: Options, store: &Store) -> Result<()> {
+    if let Some(class) = &options.class {
+        if lexicon::file_name(class).is_none() {
+            bail!("{}s can't be added to the lexicon", class);
+        }
+    }
+
+    let table = read(&options)?;
+    let columns = Columns {
+        word: import::column(&options.word, &table.header)?,
+        meaning: import::column(&options.meaning, &table.header)?,
+        class: options
+            .class_column
+            .as_deref()
+            .map(|column| import::column(column, &table.header))
+            .transpose()?,
+    };
+    let (entries, mut skipped) = import::entries(&table, columns);
+
+    // Ask for the classes that are not known, unless the input is not typed
+    let interactive = options.class.is_none() && stdin().is_terminal();
+    let mut input = stdin().lock();
+    let mut classified = vec![];
+    for mut entry in entries {
+        if entry.class.is_none() {
+            entry.class = match &options.class {
+                Some(class) => Some(class.clone()),
+                None if interactive => ask(&mut input, &mut stdout(), &entry)?,
+                None => None,
+            };
+        }
+
+        entry.set_noun_roles(options.noun_roles);
+
+        match entry.class {
+            Some(_) => classified.push(entry),
+            None => skipped.push(Skipped {
+                row: entry.row,
+                reason: format!("the class of \"{}\" is not known, pass --class", entry.word),
+            }),
+        }
+    }
+    skipped.sort_by_key(|skipped| skipped.row);
+
+    if options.dry_run {
+        import::write_lines(stdout().lock(), &classified)?;
+    } else if !classified.is_empty() {
+        let dir = options.output.unwrap_or_else(|| store.lexicon_dir());
+        let source = options
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for path in import::write_dir(&dir, &classified, &source)? {
+            println!("Added words to {}", path.display());
+        }
+    }
+    println!(
+        "Imported {} words, skipped {} rows",
+        classified.len(),
+        skipped.len()
+    );
+    // Printed apart from the word lines, so they can be redirected to a file
+    for skipped in &skipped {
+        eprintln!("{}", skipped);
+    }
+
+    Ok(())
+}
+
+/// Read the rows of the file, the kind of file is known from the extension.
+fn read(options: &Options) -> Result<Table> {
+    let extension = options
+        .file
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    if extension.as_deref() == Some("apkg") {
+        return read_apkg(&options.file);
+    }
+
+    let delimiter = match options.delimiter {
+        Some(delimiter) if delimiter.is_ascii() => delimiter as u8,
+        Some(delimiter) => bail!("The delimiter '{}' is not an ASCII character", delimiter),
+        None if extension.as_deref() == Some("tsv") => b'\t',
+        None => b',',
+    };
+
+    import::read_csv(fs::File::open(&options.file)?, delimiter, options.header)
+}
+
+#[cfg(feature = "anki")]
+fn read_apkg(path: &Path) -> Result<Table> {
+    import::read_apkg(path)
+}
+
+#[cfg(not(feature = "anki"))]
+fn read_apkg(_path: &Path) -> Result<Table> {
+    bail!("Built without the \"anki\" feature, export the deck as a text file instead")
+}
+
+/// Ask the class of the word until a valid one is typed, `None` to skip it.
+fn ask<I, O>(input: &mut I, output: &mut O, entry: &Entry) -> Result<Option<Class>>
+where
+    I: BufRead,
+    O: Write,
+{
+    writeln!(output, "Class of \"{}\" ({})?", entry.word, entry.meaning)?;
+    for (index, class) in CLASSES.iter().enumerate() {
+        writeln!(output, "  {}. {}", index + 1, class)?;
+    }
+    writeln!(
+        output,
+        "Type the number or name, or nothing to skip the word."
+    )?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let class = line
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| CLASSES.get(number.checked_sub(1)?))
+            .map(|class| class.to_string())
+            .unwrap_or_else(|| line.trim().to_string())
+            .parse::<Class>()
+            .ok()
+            .filter(|class| lexicon::file_name(class).is_some())
+            .ok_or_else(|| anyhow!("Type a number from 1 to {}.", CLASSES.len()));
+        match class {
+            Ok(class) => return Ok(Some(class)),
+            Err(err) => writeln!(output, "{}", err)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_ask() -> Result<()> {
+        let entry = Entry {
+            row: 1,
+            word: "bơi".to_string(),
+            meaning: "swim".to_string(),
+            class: None,
+        };
+        let class = |input: &str| -> Result<Option<String>> {
+            Ok(ask(&mut input.as_bytes(), &mut vec![], &entry)?.map(|class| class.to_string()))
+        };
+
+        assert_eq!(class("3\n")?, Some("verb".to_string()));
+        // Invalid answers are asked again
+        assert_eq!(class("0\nadverb\nnoun\n")?, Some("common noun".to_string()));
+        assert_eq!(class("\n")?, None);
+        assert_eq!(class("x\n")?, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use rand::{rngs::StdRng, SeedableRng};
+use std::{collections::HashSet, fmt::Display, path::Path};
+use tieng_viet::grammar::{
+    defaults_parser, lexicon,
+    phrase::{NounPhrase, VerbPhrase},
+    sentence::Sentence,
+    structured, Generate,
+};
+
+/// The kinds of words that can be left out in a lesson.
+const BLANKS: [&str; 5] = ["VERB", "TENSE", "CLASSIFIER", "DEMONSTRATIVE", "NOUN"];
+
+/// A mistake in a data file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Problem {
+    pub file: String,
+    /// Starting at 1, 0 when it's about the whole file, the number of the word in TOML and JSON
+    /// files.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// The data files that are included in the program, with their names.
+pub fn included() -> Vec<(String, String)> {
+    lexicon::FILES
+        .iter()
+        .chain(&[
+            ("sentences.txt", include_str!("../sentences.txt")),
+            ("noun_phrases.txt", include_str!("../noun_phrases.txt")),
+            ("verb_phrases.txt", include_str!("../verb_phrases.txt")),
+            ("lessons.txt", include_str!("../lessons.txt")),
+        ])
+        .map(|(name, data)| (name.to_string(), data.to_string()))
+        .collect()
+}
+
+/// Check the data files, the kind of data is known from the name of the file.
+///
+/// Classifiers of nouns must be in one of the classifier files, or in the included one when no
+/// classifier file is checked.
+pub fn lint(files: &[(String, String)]) -> Vec<Problem> {
+    let mut classifiers = files
+        .iter()
+        .filter(|(name, _)| file_name(name) == "classifiers.txt")
+        .flat_map(|(_, data)| words(data))
+        .collect::<HashSet<_>>();
+    if classifiers.is_empty() {
+        classifiers = words(include_str!("../classifiers.txt")).collect();
+    }
+
+    files
+        .iter()
+        .flat_map(|(name, data)| {
+            let mut problems = vec![];
+            let mut problem = |line, message| {
+                problems.push(Problem {
+                    file: name.clone(),
+                    line,
+                    message,
+                })
+            };
+
+            match file_name(name) {
+                "sentences.txt" => lint_structures::<Sentence, _>(data, &mut problem),
+                "noun_phrases.txt" => lint_structures::<NounPhrase, _>(data, &mut problem),
+                "verb_phrases.txt" => lint_structures::<VerbPhrase, _>(data, &mut problem),
+                "lessons.txt" => lint_lessons(data, &mut problem),
+                file if structured::is_structured(Path::new(file)) => {
+                    lint_structured(data, Path::new(file), &classifiers, &mut problem)
+                }
+                file => match lexicon::keys(file) {
+                    Some(keys) => lint_words(data, keys, &classifiers, &mut problem),
+                    None => problem(0, format!("Unknown data file {}", file)),
+                },
+            }
+
+            problems
+        })
+        .collect()
+}
+
+/// Check the words of a TOML or JSON file like the lines of a word list.
+fn lint_structured<F>(data: &str, path: &Path, classifiers: &HashSet<&str>, problem: &mut F)
+where
+    F: FnMut(usize, String),
+{
+    let lexicon = match structured::parse(path, data) {
+        Ok(lexicon) => lexicon,
+        Err(err) => return problem(0, err.to_string()),
+    };
+
+    for (index, entry) in lexicon.words.iter().enumerate() {
+        // The meanings can contain characters a line can't, they only have to be there
+        let line = entry.list().and_then(|list| {
+            let line = defaults_parser::write_word_line(
+                &entry.word,
+                &entry.metadata(),
+                entry.meanings.first().map(|_| "-"),
+            )?;
+            Ok((list, line))
+        });
+
+        match line {
+            Ok((list, line)) => {
+                if let Some(keys) = lexicon::keys(list) {
+                    lint_words(&line, keys, classifiers, &mut |_, message| {
+                        problem(index + 1, message)
+                    });
+                }
+            }
+            Err(err) => problem(index + 1, err.to_string()),
+        }
+    }
+}
+
+/// Check the metadata, meaning and spelling of every word.
+fn lint_words<F>(data: &str, keys: fn(&str) -> bool, classifiers: &HashSet<&str>, problem: &mut F)
+where
+    F: FnMut(usize, String),
+{
+    let mut seen = HashSet::new();
+    for (line, text) in defaults_parser::parse_lines(data) {
+        let (word, metadata, _) = defaults_parser::parse_word_line(text);
+
+        if !seen.insert(word) {
+            problem(line, format!("{} is defined twice", word));
+        }
+        for message in lexicon::line_problems(text, keys) {
+            problem(line, message);
+        }
+
+        for (key, data) in metadata {
+            if let ("CLASSIFIER", Some(classifier)) = (key, data) {
+                if keys(key) && !classifiers.contains(classifier) {
+                    problem(
+                        line,
+                        format!("Classifier {} is not in the classifiers", classifier),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Check that every structure can be generated.
+fn lint_structures<G, F>(data: &str, problem: &mut F)
+where
+    G: Generate<StructureItem = Vec<String>>,
+    F: FnMut(usize, String),
+{
+    let mut rng = StdRng::seed_from_u64(0);
+    for (line, text) in defaults_parser::parse_lines(data) {
+        let structure = text
+            .split('+')
+            .map(|item| item.trim().to_string())
+            .collect::<Vec<_>>();
+
+        // The head of a noun phrase must be a subject or an object
+        if let Err(err) = G::default_words(&mut rng, structure, vec!["SUBJECT", "OBJECT"]) {
+            problem(line, format!("{:#}", err));
+        }
+    }
+}
+
+/// Check that every lesson leaves out a kind of word that exists.
+fn lint_lessons<F>(data: &str, problem: &mut F)
+where
+    F: FnMut(usize, String),
+{
+    for (line, text) in defaults_parser::parse_lines(data) {
+        let (name, metadata, _) = defaults_parser::parse_word_line(text);
+
+        let mut blanks = 0;
+        for (key, data) in metadata {
+            match (key, data) {
+                ("BLANK", Some(blank)) if BLANKS.contains(&blank) => blanks += 1,
+                ("BLANK", _) => problem(
+                    line,
+                    format!(
+                        "BLANK must be one of {}, \"BLANK = VERB\"",
+                        BLANKS.join(", ")
+                    ),
+                ),
+                ("CHOICE", None) => (),
+                (key, _) => problem(line, format!("Unknown metadata {}", key)),
+            }
+        }
+        if blanks == 0 {
+            problem(line, format!("Lesson {} doesn't leave out any words", name));
+        }
+    }
+}
+
+/// The words defined in a word file.
+fn words(data: &str) -> impl Iterator<Item = &str> {
+    defaults_parser::parse_str(data).map(|line| defaults_parser::parse_word_line(line).0)
+}
+
+/// The name of the file without the directory.
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_included() {
+        assert_eq!(lint(&included()), vec![]);
+    }
+
+    #[test]
+    fn test_problems() {
+        let file = |name: &str, data: &str| vec![(name.to_string(), data.to_string())];
+
+        let problems = lint(&file(
+            "data/common_nouns.txt",
+            "bàn(ITEM + CLASSIFIER = cái): table\nbàn(ITEM + SIZE): table\nxyz(CLASSIFIER = abc)",
+        ));
+        let messages = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(messages[0], (2, "bàn is defined twice"));
+        assert_eq!(messages[1], (2, "Unknown metadata SIZE"));
+        assert_eq!(messages[2], (3, "xyz has no meaning"));
+        assert!(messages[3].1.starts_with("xyz is not spelled right"));
+        assert_eq!(messages[4], (3, "Classifier abc is not in the classifiers"));
+        assert_eq!(
+            problems[0].to_string(),
+            "data/common_nouns.txt:2: bàn is defined twice"
+        );
+
+        assert_eq!(
+            lint(&file("noun_phrases.txt", "HEAD\nHEAD + VERB")).len(),
+            1
+        );
+        assert_eq!(lint(&file("lessons.txt", "Verbs(BLANK = ADVERB)")).len(), 2);
+        assert_eq!(lint(&file("unknown.txt", "")).len(), 1);
+
+        let problems = lint(&file(
+            "words.toml",
+            "[[words]]\nword = \"chó\"\nclass = \"common noun\"\nmeanings = [\"dog #1\"]\n\
+             [[words]]\nword = \"xyz\"\nclass = \"verb\"\nproperties = { INTENSIVE = true }\n",
+        ));
+        let messages = problems
+            .iter()
+            .map(|problem| (problem.line, problem.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(messages[0], (2, "xyz has no meaning"));
+        assert!(messages[1].1.starts_with("xyz is not spelled right"));
+        assert_eq!(messages[2], (2, "Unknown metadata INTENSIVE"));
+        assert_eq!(messages.len(), 3);
+        assert_eq!(lint(&file("words.json", r#"{"words": 1}"#)).len(), 1);
+    }
+}
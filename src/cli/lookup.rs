@@ -0,0 +1,136 @@
+use anyhow::{bail, Result};
+use tieng_viet::{
+    grammar::{
+        syllable,
+        word::{Class, Word},
+    },
+    settings::Settings,
+};
+
+/// Print everything known about the words that match.
+pub fn run(query: &str, settings: &Settings) -> Result<()> {
+    let words = find(query);
+    if words.is_empty() {
+        bail!("No word matches \"{}\"", query);
+    }
+
+    let details = words
+        .iter()
+        .map(|word| describe(word, settings))
+        .collect::<Result<Vec<_>>>()?;
+    print!("{}", details.join("\n"));
+
+    Ok(())
+}
+
+/// Find the words by how they're written, falling back to without diacritics and the meaning.
+pub fn find(query: &str) -> Vec<Word> {
+    let words = Word::defaults()
+        .filter(|word| word.accepts(query, None))
+        .collect::<Vec<_>>();
+    if !words.is_empty() {
+        return words;
+    }
+
+    let ascii = syllable::to_ascii(&syllable::normalize(query));
+    let words = Word::defaults()
+        .filter(|word| {
+            word.accepted_forms(None)
+                .into_iter()
+                .any(|form| syllable::to_ascii(&syllable::normalize(form)) == ascii)
+        })
+        .collect::<Vec<_>>();
+    if !words.is_empty() {
+        return words;
+    }
+
+    let english = query.trim().to_lowercase();
+    Word::defaults()
+        .filter(|word| {
+            word.meanings()
+                .flat_map(|meaning| meaning.split(&[',', ';'][..]))
+                .any(|meaning| meaning.trim().to_lowercase() == english)
+        })
+        .collect()
+}
+
+/// The details of the word as lines of text.
+fn describe(word: &Word, settings: &Settings) -> Result<String> {
+    let mut text = format!("{}: {}\n", word, word.meaning());
+    let other_meanings = word.meanings().skip(1).collect::<Vec<_>>();
+    if !other_meanings.is_empty() {
+        text.push_str(&format!("Also: {}\n", other_meanings.join(", ")));
+    }
+    text.push_str(&format!("Class: {}\n", word.class()));
+
+    let mut properties = vec![];
+    match word.class() {
+        Class::ProperNoun(noun) => {
+            properties.extend(noun.is_subject.then(|| "subject".to_string()));
+            properties.extend(noun.is_object.then(|| "object".to_string()));
+            properties.extend(noun.is_person.then(|| "person".to_string()));
+        }
+        Class::CommonNoun(noun) => {
+            properties.push(format!("{:?}", noun.kind).to_lowercase());
+            properties.extend(noun.is_subject.then(|| "subject".to_string()));
+            properties.extend(noun.is_object.then(|| "object".to_string()));
+        }
+        Class::Adjective(adjective) => {
+            properties.extend(adjective.intensifiable.then(|| "intensifiable".to_string()))
+        }
+        _ => (),
+    }
+    if !properties.is_empty() {
+        text.push_str(&format!("Properties: {}\n", properties.join(", ")));
+    }
+
+    if let Class::Verb(verb) = word.class() {
+        if let Some(past) = &verb.past {
+            text.push_str(&format!("Past tense: {}\n", past));
+        }
+    }
+    if let Some(classifier) = word.classifier() {
+        text.push_str(&format!("Classifier: {}\n", classifier));
+    }
+    for (dialect, variant) in word.variants() {
+        text.push_str(&format!("{}: {}\n", dialect, variant));
+    }
+    if let Some(etymology) = word.etymology() {
+        text.push_str(&format!("Origin: {}\n", etymology));
+    }
+    text.push_str(&format!(
+        "{} pronunciation: {}\n",
+        settings.dialect,
+        word.pronunciation(settings.dialect)?
+    ));
+    for example in word.examples() {
+        text.push_str(&format!(
+            "Example: {}: {}\n",
+            example.vietnamese, example.english
+        ));
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let found = |query| {
+            find(query)
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(found("sách"), vec!["sách"]);
+        // Variants of other dialects are found
+        assert_eq!(found("chén"), vec!["bát"]);
+        assert_eq!(found("sach"), vec!["sách", "sạch"]);
+        assert_eq!(found("Book"), vec!["sách"]);
+        assert!(found("xyz").is_empty());
+    }
+}
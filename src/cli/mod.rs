@@ -0,0 +1,224 @@
+mod convert;
+mod export;
+mod generate;
+mod import;
+mod lint;
+mod lookup;
+mod quiz;
+mod stats;
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{
+    fs,
+    io::{stdin, stdout},
+    path::PathBuf,
+};
+use tieng_viet::{
+    drill::Drill,
+    grammar::lexicon,
+    profile::{Profile, Store},
+    settings::Settings,
+};
+
+/// Practice Vietnamese grammar.
+///
+/// Without a command the interactive full-screen program is started.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Use this profile instead of the one that was used last.
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// What to do instead of starting the interactive program.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print generated sentences.
+    Generate {
+        /// How many sentences are printed.
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Generate the same sentences every time with this seed, the seed of every next
+        /// sentence is one higher.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Only print sentences formed with a structure, like "CLASSIFIER + HEAD".
+        #[arg(long)]
+        structure: Option<String>,
+        /// Also print the classes of the words, the translation, the structures and the seed.
+        #[arg(long)]
+        metadata: bool,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Practice by typing the answers, works over a plain pipe.
+    Quiz {
+        /// The name or number of the drill, a random drill is used for every exercise by default.
+        #[arg(long)]
+        drill: Option<String>,
+        /// Stop after this many exercises, otherwise it stops at the end of the input.
+        #[arg(short = 'n', long)]
+        count: Option<usize>,
+    },
+    /// Check the data files for mistakes, the included data files when none are passed.
+    Lint {
+        /// Data files, which kind of data they contain is known from the name like `verbs.txt`.
+        files: Vec<PathBuf>,
+    },
+    /// Convert a word list between word lines and the structured TOML or JSON format.
+    Convert {
+        /// A word list like `verbs.txt`, or a TOML or JSON file.
+        file: PathBuf,
+        /// The format that's printed.
+        #[arg(long, value_enum)]
+        to: convert::LexiconFormat,
+    },
+    /// Export the words or generated sentences as Anki cards or as a worksheet.
+    Export(export::Options),
+    /// Add words from a word list or an Anki deck to the lexicon.
+    Import(import::Options),
+    /// Show the class, meaning and other details of a word.
+    Lookup {
+        /// The word in Vietnamese, with or without diacritics, or its English meaning.
+        word: String,
+    },
+    /// Print the statistics of the profile.
+    Stats,
+}
+
+/// How generated sentences are printed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// A sentence per line.
+    Text,
+    /// The sentence, translation and structures separated by tabs.
+    Tsv,
+    /// A JSON object per sentence with the words, pronunciations, translation, structures and
+    /// seed.
+    #[value(name = "jsonl")]
+    JsonLines,
+    /// The same fields in columns with a header, the words are separated by semicolons.
+    Csv,
+}
+
+impl Command {
+    /// Run the command with the profile, it's saved when the command changes it.
+    pub fn run(self, profile: Option<&str>) -> Result<()> {
+        match self {
+            Command::Generate {
+                count,
+                seed,
+                structure,
+                metadata,
+                format,
+            } => {
+                // Without a data directory the sentences are generated with the default settings
+                let settings = match Store::open() {
+                    Ok(store) => open(&store, profile)?.1,
+                    Err(_) => Settings::default().with_env()?,
+                };
+                generate::run(
+                    count,
+                    seed,
+                    structure.as_deref(),
+                    metadata,
+                    format,
+                    &settings,
+                )
+            }
+            Command::Quiz { drill, count } => {
+                let store = Store::open()?;
+                let (mut profile, settings) = open(&store, profile)?;
+                let drills = match drill {
+                    Some(name) => vec![quiz::find_drill(&name)?],
+                    None => Drill::all(),
+                };
+                let summary = quiz::quiz(
+                    &mut rand::thread_rng(),
+                    stdin().lock(),
+                    &mut stdout(),
+                    &drills,
+                    count,
+                    &settings,
+                    &mut profile,
+                )?;
+                if summary.answered > 0 {
+                    store.save(&profile)?;
+                }
+
+                Ok(())
+            }
+            Command::Lint { files } => {
+                let problems = if files.is_empty() {
+                    lint::lint(&lint::included())
+                } else {
+                    let files = files
+                        .iter()
+                        .map(|path| Ok((path.display().to_string(), fs::read_to_string(path)?)))
+                        .collect::<Result<Vec<_>>>()?;
+                    lint::lint(&files)
+                };
+
+                for problem in &problems {
+                    println!("{}", problem);
+                }
+                if !problems.is_empty() {
+                    bail!("Found {} problems in the data files", problems.len());
+                }
+
+                Ok(())
+            }
+            Command::Convert { file, to } => convert::run(&file, to),
+            Command::Export(options) => {
+                let (_, settings) = open(&Store::open()?, profile)?;
+                export::run(options, &settings)
+            }
+            Command::Import(options) => {
+                let store = Store::open()?;
+                extend_lexicon(&store)?;
+                import::run(options, &store)
+            }
+            Command::Lookup { word } => {
+                let (_, settings) = open(&Store::open()?, profile)?;
+                lookup::run(&word, &settings)
+            }
+            Command::Stats => stats::run(&load(&Store::open()?, profile)?),
+        }
+    }
+}
+
+/// Add the words of the user to the lexicon.
+fn extend_lexicon(store: &Store) -> Result<()> {
+    // A broken word list of the user shouldn't stop the commands that don't need it
+    let (words, errors) = lexicon::read_dir(&store.lexicon_dir());
+    for err in errors {
+        eprintln!("Warning: {:#}, its words are left out", err);
+    }
+
+    lexicon::extend(words)
+}
+
+/// Add the words of the user to the lexicon and load the profile, the one that was used last
+/// when none is passed.
+pub fn load(store: &Store, profile: Option<&str>) -> Result<Profile> {
+    extend_lexicon(store)?;
+
+    match profile {
+        Some(name) => store.load(name),
+        None => store.load(&store.current()),
+    }
+}
+
+/// Load the profile with its settings.
+fn open(store: &Store, profile: Option<&str>) -> Result<(Profile, Settings)> {
+    let mut profile = load(store, profile)?;
+    // The environment variables override the settings of the profile
+    let settings = profile.settings.clone().with_env()?;
+    profile.schedule.algorithm = settings.algorithm;
+
+    Ok((profile, settings))
+}
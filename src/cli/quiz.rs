@@ -0,0 +1,264 @@
+use anyhow::{anyhow, bail, Result};
+use rand::{seq::SliceRandom, Rng};
+use std::io::{BufRead, Write};
+use tieng_viet::{
+    drill::{Drill, Exercise, Grade, Input, Response, Summary},
+    grammar::syllable,
+    profile::Profile,
+    settings::Settings,
+    srs::{self, Item},
+};
+
+/// Find a drill by its number or its name, diacritics and case don't matter.
+pub fn find_drill(name: &str) -> Result<Drill> {
+    let drills = Drill::all();
+    if let Some(drill) = name
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| drills.get(number.checked_sub(1)?))
+    {
+        return Ok(*drill);
+    }
+
+    let ascii = syllable::to_ascii(&name.to_lowercase());
+    let names = drills
+        .iter()
+        .map(|drill| syllable::to_ascii(&drill.to_string().to_lowercase()))
+        .collect::<Vec<_>>();
+    // A drill with exactly the name is preferred over drills with the name in it
+    let found = match names.iter().position(|drill| *drill == ascii) {
+        Some(index) => vec![index],
+        None => (0..drills.len())
+            .filter(|index| names[*index].contains(&ascii))
+            .collect(),
+    };
+
+    match found.as_slice() {
+        [index] => Ok(drills[*index]),
+        _ => bail!(
+            "No single drill matches \"{}\", the drills are:\n{}",
+            name,
+            drills
+                .iter()
+                .enumerate()
+                .map(|(index, drill)| format!("{}. {}", index + 1, drill))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }
+}
+
+/// Ask exercises of the drills and read the answers line by line, until the input ends.
+///
+/// The answers are recorded in the profile.
+pub fn quiz<R, I, O>(
+    rng: &mut R,
+    mut input: I,
+    output: &mut O,
+    drills: &[Drill],
+    count: Option<usize>,
+    settings: &Settings,
+    profile: &mut Profile,
+) -> Result<Summary>
+where
+    R: Rng,
+    I: BufRead,
+    O: Write,
+{
+    let mut summary = Summary::default();
+    while count.is_none_or(|count| summary.answered < count) {
+        let drill = *drills
+            .choose(rng)
+            .ok_or_else(|| anyhow!("There are no drills to practice"))?;
+        let exercise = drill.exercise(rng, settings, &profile.schedule)?;
+
+        writeln!(output, "{} {}", drill, summary.answered + 1)?;
+        ask(output, &exercise)?;
+        let response = match read_response(&mut input, output, &exercise)? {
+            Some(response) => response,
+            None => break,
+        };
+
+        let grade = exercise.grade(&response);
+        summary.record(grade == Grade::Correct);
+        let now = srs::now();
+        let items = Item::all(&exercise.words);
+        profile
+            .schedule
+            .review_all(items.clone(), grade.into(), now);
+        profile.record(drill.to_string(), items, grade, now);
+
+        feedback(output, &exercise, grade)?;
+    }
+
+    writeln!(
+        output,
+        "Answered: {}, correct: {} ({:.0}%)",
+        summary.answered,
+        summary.correct,
+        summary.accuracy()
+    )?;
+
+    Ok(summary)
+}
+
+/// Print the question with the options that can be picked.
+fn ask<O>(output: &mut O, exercise: &Exercise) -> Result<()>
+where
+    O: Write,
+{
+    writeln!(output, "{}", exercise.prompt)?;
+    match &exercise.input {
+        Input::Choice { options, .. } => {
+            for (index, option) in options.iter().enumerate() {
+                writeln!(output, "  {}. {}", index + 1, option)?;
+            }
+            writeln!(output, "Type the number of the answer.")?;
+        }
+        Input::Order { words, .. } => {
+            for (index, word) in words.iter().enumerate() {
+                writeln!(output, "  {}. {}", index + 1, word)?;
+            }
+            writeln!(output, "Type the numbers of the words in order.")?;
+        }
+        Input::Text { .. } | Input::English { .. } | Input::Vietnamese { .. } => (),
+    }
+
+    Ok(())
+}
+
+/// Read lines until one is a valid answer, `None` when the input ends.
+fn read_response<I, O>(
+    input: &mut I,
+    output: &mut O,
+    exercise: &Exercise,
+) -> Result<Option<Response>>
+where
+    I: BufRead,
+    O: Write,
+{
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(None);
+        }
+
+        match parse_response(&exercise.input, line.trim()) {
+            Ok(response) => return Ok(Some(response)),
+            Err(err) => writeln!(output, "{}", err)?,
+        }
+    }
+}
+
+/// Turn a typed line into a response, options are picked by their number.
+fn parse_response(input: &Input, line: &str) -> Result<Response> {
+    // Turn the number "2" into index 1
+    let index = |number: &str, len: usize| {
+        number
+            .parse::<usize>()
+            .ok()
+            .filter(|number| (1..=len).contains(number))
+            .map(|number| number - 1)
+    };
+
+    match input {
+        Input::Choice { options, .. } => index(line, options.len())
+            // The option can also be typed
+            .or_else(|| {
+                options
+                    .iter()
+                    .position(|option| syllable::normalize(option) == syllable::normalize(line))
+            })
+            .map(Response::Choice)
+            .ok_or_else(|| anyhow!("Type a number from 1 to {}.", options.len())),
+        Input::Order { words, .. } => line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|number| !number.is_empty())
+            .map(|number| index(number, words.len()))
+            .collect::<Option<Vec<_>>>()
+            .filter(|order| order.len() == words.len())
+            .map(Response::Order)
+            .ok_or_else(|| anyhow!("Type all numbers from 1 to {} in order.", words.len())),
+        Input::Text { .. } | Input::English { .. } | Input::Vietnamese { .. } => {
+            Ok(Response::Text(line.to_string()))
+        }
+    }
+}
+
+/// Print whether the answer was correct with an explanation.
+fn feedback<O>(output: &mut O, exercise: &Exercise, grade: Grade) -> Result<()>
+where
+    O: Write,
+{
+    match grade {
+        Grade::Correct => writeln!(output, "Correct!")?,
+        Grade::AlmostCorrect => writeln!(output, "Almost, check the tones: {}", exercise.answer())?,
+        Grade::Wrong => writeln!(output, "Wrong, the answer is: {}", exercise.answer())?,
+    }
+    writeln!(output, "{}", exercise.explanation)?;
+    if let Some(translation) = &exercise.translation {
+        writeln!(output, "{}", translation)?;
+    }
+    writeln!(output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_find_drill() -> Result<()> {
+        assert_eq!(find_drill("1")?, Drill::all()[0]);
+        assert_eq!(find_drill("word ORDER")?, Drill::Scramble);
+        // Also the name of a fill in the blank lesson, but this one matches exactly
+        assert_eq!(find_drill("classifiers")?, Drill::Classifier);
+        assert!(find_drill("english").is_err());
+        assert!(find_drill("0").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quiz() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let settings = Settings::default();
+        let mut profile = Profile::new("test")?;
+        let mut output = vec![];
+
+        // Invalid answers are asked again, the quiz stops at the end of the input
+        let summary = quiz(
+            &mut rng,
+            "x\n1\n2\n".as_bytes(),
+            &mut output,
+            &[Drill::Classifier],
+            None,
+            &settings,
+            &mut profile,
+        )?;
+        assert_eq!(summary.answered, 2);
+        assert_eq!(profile.history.len(), 2);
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("Type a number from 1 to"));
+
+        // Or after the amount of exercises
+        let summary = quiz(
+            &mut rng,
+            "1\n2\n".as_bytes(),
+            &mut vec![],
+            &[Drill::Classifier],
+            Some(1),
+            &settings,
+            &mut profile,
+        )?;
+        assert_eq!(summary.answered, 1);
+
+        Ok(())
+    }
+}
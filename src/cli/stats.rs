@@ -0,0 +1,105 @@
+use anyhow::Result;
+use tieng_viet::{drill::Summary, profile::Profile, srs, stats::Statistics};
+
+/// How many of the weakest words are printed.
+const WORDS: usize = 20;
+
+/// Print the totals and the accuracy by what was practiced.
+pub fn run(profile: &Profile) -> Result<()> {
+    print!("{}", report(profile));
+
+    Ok(())
+}
+
+/// The statistics of the profile as text.
+fn report(profile: &Profile) -> String {
+    let statistics = Statistics::new(&profile.history);
+    let now = srs::now();
+
+    let mut text = format!(
+        "Profile: {}\nAnswered: {}\nAccuracy: {:.0}%\nItems reviewed: {}\nDue for review: {}\nStreak: {} days, longest {} days\n",
+        profile.name,
+        statistics.total.answered,
+        statistics.total.accuracy(),
+        profile.schedule.reviews.len(),
+        profile.schedule.due(now).len(),
+        profile.streak.today(now),
+        profile.streak.longest,
+    );
+    text.push_str(&table("Word classes", statistics.classes.iter()));
+    text.push_str(&table("Structures", statistics.structures.iter()));
+    text.push_str(&table(
+        "Tones",
+        statistics
+            .tones
+            .iter()
+            .map(|(tone, summary)| (tone.name(), summary)),
+    ));
+    text.push_str(&table(
+        "Weakest words",
+        statistics
+            .weakest_words()
+            .iter()
+            .take(WORDS)
+            .map(|(id, summary)| (id, summary)),
+    ));
+
+    text
+}
+
+/// A header with the accuracy and number of reviews of every row, empty without rows.
+fn table<'a, I, S>(title: &str, rows: I) -> String
+where
+    I: Iterator<Item = (S, &'a Summary)>,
+    S: AsRef<str>,
+{
+    let rows = rows
+        .map(|(name, summary)| (name.as_ref().to_string(), *summary))
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        return String::new();
+    }
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut text = format!("\n{}:\n", title);
+    for (name, summary) in rows {
+        text.push_str(&format!(
+            "  {:width$} {:>3.0}% {:>4} reviews\n",
+            name,
+            summary.accuracy(),
+            summary.answered,
+            width = width
+        ));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tieng_viet::{drill::Grade, srs::Item};
+
+    #[test]
+    fn test_report() -> Result<()> {
+        let mut profile = Profile::new("test")?;
+        assert!(!report(&profile).contains("Word classes"));
+
+        profile.record(
+            "Word order".to_string(),
+            vec![Item::Structure("HEAD (noun_phrases.txt)".to_string())],
+            Grade::Correct,
+            srs::now(),
+        );
+        let report = report(&profile);
+        assert!(report.contains("Answered: 1\n"));
+        assert!(report.contains("  HEAD (noun_phrases.txt) 100%    1 reviews\n"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    drill::Input,
+    grammar::{dialect::Dialect, syllable, word::Word},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// How many wrong options are shown next to the right one.
+const DISTRACTORS: usize = 3;
+
+/// A multiple choice question for a word, with words of the same class as wrong options.
+///
+/// Words that are spelled alike or have the same tones are preferred, words that would also be
+/// a correct answer, like the variant in another dialect, are never shown. The words of the
+/// options are returned in the same order as the options.
+pub fn words<R>(rng: &mut R, correct: &Word, dialect: Dialect) -> (Input, Vec<Word>)
+where
+    R: Rng,
+{
+    let mut candidates = Word::defaults()
+        .filter(|word| same_class(word, correct))
+        .map(|word| word.in_dialect(dialect))
+        .filter(|word| {
+            !correct.accepts(&word.to_string(), None) && !word.accepts(&correct.to_string(), None)
+        })
+        .collect::<Vec<_>>();
+
+    // Shuffle first so equally similar words are picked randomly
+    candidates.shuffle(rng);
+    candidates.sort_by_key(|word| std::cmp::Reverse(similarity(correct, word)));
+    let mut distractors: Vec<Word> = vec![];
+    for candidate in candidates {
+        if distractors.len() == DISTRACTORS {
+            break;
+        }
+        if !distractors
+            .iter()
+            .any(|word| word.to_string() == candidate.to_string())
+        {
+            distractors.push(candidate);
+        }
+    }
+
+    let mut words = distractors;
+    words.push(correct.clone());
+    words.shuffle(rng);
+
+    let options = words
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>();
+    let correct = options
+        .iter()
+        .position(|option| *option == correct.to_string())
+        .unwrap_or_default();
+
+    (Input::Choice { options, correct }, words)
+}
+
+/// A multiple choice question with the wrong options already chosen.
+///
+/// Wrong options that are the same as the correct one or as each other are left out.
+pub fn options<R, I>(rng: &mut R, correct: String, distractors: I) -> Input
+where
+    R: Rng,
+    I: IntoIterator<Item = String>,
+{
+    let mut options: Vec<String> = vec![];
+    for distractor in distractors {
+        if options.len() == DISTRACTORS {
+            break;
+        }
+        let normalized = syllable::normalize(&distractor);
+        if normalized != syllable::normalize(&correct)
+            && !options
+                .iter()
+                .any(|option| syllable::normalize(option) == normalized)
+        {
+            options.push(distractor);
+        }
+    }
+    options.push(correct.clone());
+    options.shuffle(rng);
+
+    let correct = options
+        .iter()
+        .position(|option| *option == correct)
+        .unwrap_or_default();
+
+    Input::Choice { options, correct }
+}
+
+/// Whether the words are of the same kind, regardless of how they are used in a sentence.
+fn same_class(word: &Word, other: &Word) -> bool {
+    std::mem::discriminant(word.class()) == std::mem::discriminant(other.class())
+}
+
+/// How easily the words can be confused, higher is more similar.
+fn similarity(word: &Word, other: &Word) -> usize {
+    let tones = |word: &Word| {
+        word.syllables()
+            .map(|syllables| {
+                syllables
+                    .iter()
+                    .map(|syllable| syllable.tone)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    let spelling = |word: &Word| syllable::strip_tones(&syllable::normalize(&word.to_string()));
+
+    let mut similarity = 0;
+    if tones(word) == tones(other) {
+        similarity += 2;
+    }
+    similarity + 3usize.saturating_sub(distance(&spelling(word), &spelling(other)))
+}
+
+/// The amount of characters that have to be changed to get from one text to the other.
+///
+/// See: <https://en.wikipedia.org/wiki/Levenshtein_distance>
+fn distance(text: &str, other: &str) -> usize {
+    let other = other.chars().collect::<Vec<_>>();
+    let mut previous = (0..=other.len()).collect::<Vec<_>>();
+
+    for (i, c) in text.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, o) in other.iter().enumerate() {
+            let substitution = previous[j] + usize::from(c != *o);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[other.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Result};
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("con", "con"), 0);
+        assert_eq!(distance("con", "cai"), 2);
+        assert_eq!(distance("", "bat"), 3);
+    }
+
+    #[test]
+    fn test_words() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let bowl = Word::defaults()
+            .find(|word| word.to_string() == "bát")
+            .ok_or_else(|| anyhow!("Word not found"))?;
+
+        for _ in 0..20 {
+            let (input, words) = words(&mut rng, &bowl, Dialect::Northern);
+            if let Input::Choice { options, correct } = input {
+                assert_eq!(options.len(), DISTRACTORS + 1);
+                assert_eq!(options[correct], "bát");
+                // The Southern variant is also correct so it can't be a wrong option
+                assert!(!options.contains(&"chén".to_string()));
+                assert!(words.iter().all(|word| same_class(word, &bowl)));
+            } else {
+                panic!("Expected a multiple choice question");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_options() {
+        let mut rng = rand::thread_rng();
+        let input = options(
+            &mut rng,
+            "đỏ".to_string(),
+            vec!["Đỏ".to_string(), "xanh".to_string(), "xanh".to_string()],
+        );
+
+        if let Input::Choice { options, correct } = input {
+            // Duplicates of the correct and the other options are left out
+            assert_eq!(options.len(), 2);
+            assert_eq!(options[correct], "đỏ");
+        } else {
+            panic!("Expected a multiple choice question");
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    drill::{choice, Exercise},
+    grammar::word::Word,
+    settings::Settings,
+    srs::{Item, Schedule},
+};
+use anyhow::{anyhow, Result};
+use rand::Rng;
+
+/// Ask which classifier goes with a noun.
+pub fn exercise<R>(rng: &mut R, settings: &Settings, schedule: &Schedule) -> Result<Exercise>
+where
+    R: Rng,
+{
+    // Pick a random noun that has a classifier
+    let (noun, classifier) = schedule
+        .choose(
+            rng,
+            Word::defaults()
+                .filter(|word| word.has_classifier() && settings.allows(word))
+                .filter_map(|word| word.classifier().map(|classifier| (word, classifier))),
+            |(noun, classifier)| Item::all(&[noun.clone(), classifier.clone()]),
+        )
+        .ok_or_else(|| anyhow!("Could not find a noun with a classifier"))?;
+    let noun = noun.in_dialect(settings.dialect);
+    let classifier = classifier.in_dialect(settings.dialect);
+
+    // Show the right classifier between a few similar ones
+    let (input, classifiers) = choice::words(rng, &classifier, settings.dialect);
+
+    // Explain what all shown classifiers are used for
+    let explanation = format!(
+        "{} takes the classifier {}, which is used for {}: {} {}. {}",
+        noun,
+        classifier,
+        classifier.meaning(),
+        classifier,
+        noun,
+        classifiers
+            .iter()
+            .filter(|word| word.to_string() != classifier.to_string())
+            .map(|word| format!("{} is used for {}.", word, word.meaning()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    Ok(Exercise {
+        prompt: format!("Which classifier goes with {} ({})?", noun, noun.meaning()),
+        input,
+        explanation,
+        translation: Some(format!("{} {}: {}", classifier, noun, noun.meaning())),
+        words: vec![classifier, noun],
+    })
+}
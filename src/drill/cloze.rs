@@ -0,0 +1,131 @@
+use crate::{
+    drill::{choice, Exercise, Input},
+    grammar::{defaults_parser, gloss, word::Class},
+    settings::Settings,
+    srs::Schedule,
+};
+use anyhow::{anyhow, Result};
+use rand::{seq::IteratorRandom, Rng};
+
+/// How many sentences are generated to find one with a word that can be blanked.
+const ATTEMPTS: usize = 100;
+
+/// A set of fill in the blank exercises that blank out the same kinds of words.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lesson {
+    /// Shown in the drill menu.
+    pub name: String,
+    /// The kinds of words that are blanked, "VERB" or "CLASSIFIER".
+    blanks: Vec<String>,
+    /// Whether the missing word is picked from options instead of typed.
+    choice: bool,
+}
+
+impl Lesson {
+    /// Parse the included lessons file.
+    pub fn all() -> Vec<Lesson> {
+        defaults_parser::parse_str(include_str!("../lessons.txt"))
+            .map(|line| {
+                let (name, metadata, _) = defaults_parser::parse_word_line(line);
+
+                Lesson {
+                    name: name.to_string(),
+                    blanks: metadata
+                        .iter()
+                        .filter(|(key, _)| *key == "BLANK")
+                        .filter_map(|(_, data)| data.map(|data| data.to_string()))
+                        .collect(),
+                    choice: metadata.contains(&("CHOICE", None)),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether words of the class are blanked in this lesson.
+    pub fn blanks(&self, class: &Class) -> bool {
+        self.blanks.iter().any(|blank| {
+            matches!(
+                (blank.as_str(), class),
+                ("VERB", Class::Verb(_))
+                    | ("TENSE", Class::TenseMarker(_))
+                    | ("CLASSIFIER", Class::ClassifierNoun(_))
+                    | ("DEMONSTRATIVE", Class::Demonstrative(_))
+                    | ("NOUN", Class::ProperNoun(_))
+                    | ("NOUN", Class::CommonNoun(_))
+            )
+        })
+    }
+}
+
+/// Ask to fill in a word of a generated sentence.
+pub fn exercise<R>(
+    rng: &mut R,
+    settings: &Settings,
+    schedule: &Schedule,
+    lesson: &Lesson,
+) -> Result<Exercise>
+where
+    R: Rng,
+{
+    // Generate sentences until one has a word that can be blanked
+    let (words, blank) = (0..ATTEMPTS)
+        .find_map(|_| {
+            let words = schedule.sentence(rng, settings).ok()?;
+            let blank = words
+                .iter()
+                .enumerate()
+                .filter(|(_, word)| lesson.blanks(word.class()))
+                .map(|(index, _)| index)
+                .choose(rng)?;
+
+            Some((words, blank))
+        })
+        .ok_or_else(|| anyhow!("Could not generate a sentence for lesson {}", lesson.name))?;
+    let word = words[blank].clone();
+
+    let sentence = words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            if index == blank {
+                "___".to_string()
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let translation = gloss::gloss(&words);
+
+    let input = if lesson.choice {
+        choice::words(rng, &word, settings.dialect).0
+    } else {
+        // Also accept the word as it's used in other dialects if allowed
+        let mut accepted = vec![word.to_string()];
+        for dialect in settings.answer_dialects() {
+            let variant = word.in_dialect(dialect).to_string();
+            if !accepted.contains(&variant) {
+                accepted.push(variant);
+            }
+        }
+
+        Input::Text { accepted }
+    };
+
+    Ok(Exercise {
+        prompt: format!("Fill in the blank: {} ({})", sentence, translation),
+        input,
+        explanation: if word.meaning().is_empty() {
+            format!("The missing word is the {} {}.", word.class(), word)
+        } else {
+            format!(
+                "The missing word is the {} {}: {}.",
+                word.class(),
+                word,
+                word.meaning()
+            )
+        },
+        translation: Some(translation),
+        words,
+    })
+}
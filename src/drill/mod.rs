@@ -0,0 +1,454 @@
+//! Exercises to practice with and how their answers are graded.
+
+mod choice;
+mod classifier;
+mod cloze;
+mod reduplication;
+mod scramble;
+mod translation;
+
+use crate::{
+    answer,
+    grammar::{dialect::Dialect, gloss, sentence::Sentence, syllable, word::Word, Generate},
+    settings::Settings,
+    srs::Schedule,
+};
+use anyhow::{anyhow, Result};
+use cloze::Lesson;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A type of exercise the learner can practice.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Drill {
+    /// Pick the classifier that goes with a noun.
+    Classifier,
+    /// Fill in the missing word of a sentence, the index of the lesson.
+    Cloze(usize),
+    /// Form or interpret reduplicated adjectives.
+    Reduplication,
+    /// Put the shuffled words of a sentence in the right order.
+    Scramble,
+    /// Translate a Vietnamese sentence to English.
+    ToEnglish,
+    /// Translate an English sentence to Vietnamese.
+    ToVietnamese,
+}
+
+impl Drill {
+    /// All drills that can be chosen.
+    pub fn all() -> Vec<Drill> {
+        vec![
+            Drill::ToEnglish,
+            Drill::ToVietnamese,
+            Drill::Scramble,
+            Drill::Classifier,
+            Drill::Reduplication,
+        ]
+        .into_iter()
+        .chain((0..Lesson::all().len()).map(Drill::Cloze))
+        .collect()
+    }
+
+    /// Generate a random exercise for this drill.
+    ///
+    /// Words and structures that are due for a review are preferred.
+    pub fn exercise<R>(
+        self,
+        rng: &mut R,
+        settings: &Settings,
+        schedule: &Schedule,
+    ) -> Result<Exercise>
+    where
+        R: Rng,
+    {
+        match self {
+            Drill::Classifier => classifier::exercise(rng, settings, schedule),
+            Drill::Cloze(lesson) => {
+                let lesson = Lesson::all()
+                    .into_iter()
+                    .nth(lesson)
+                    .ok_or_else(|| anyhow!("Lesson {} doesn't exist", lesson))?;
+                cloze::exercise(rng, settings, schedule, &lesson)
+            }
+            Drill::Reduplication => reduplication::exercise(rng, settings, schedule),
+            Drill::Scramble => scramble::exercise(rng, settings, schedule),
+            Drill::ToEnglish => translation::to_english(rng, settings, schedule),
+            Drill::ToVietnamese => translation::to_vietnamese(rng, settings, schedule),
+        }
+    }
+}
+
+impl Display for Drill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Drill::Classifier => write!(f, "Classifiers"),
+            Drill::Cloze(lesson) => match Lesson::all().get(*lesson) {
+                Some(lesson) => write!(f, "Fill in the blank: {}", lesson.name),
+                None => write!(f, "Fill in the blank"),
+            },
+            Drill::Reduplication => write!(f, "Reduplication"),
+            Drill::Scramble => write!(f, "Word order"),
+            Drill::ToEnglish => write!(f, "Vietnamese → English"),
+            Drill::ToVietnamese => write!(f, "English → Vietnamese"),
+        }
+    }
+}
+
+/// A single question for the learner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exercise {
+    /// The question shown to the learner.
+    pub prompt: String,
+    /// How the learner answers the question.
+    pub input: Input,
+    /// Why the answer is correct, shown after answering.
+    pub explanation: String,
+    /// English translation of the Vietnamese in the exercise.
+    pub translation: Option<String>,
+    /// Vietnamese words of the exercise, shown with their pronunciation after answering.
+    pub words: Vec<Word>,
+}
+
+impl Exercise {
+    /// How well the response answers the exercise.
+    pub fn grade(&self, response: &Response) -> Grade {
+        match (&self.input, response) {
+            (Input::Text { accepted }, Response::Text(answer)) => {
+                let answer = syllable::normalize(answer);
+                accepted
+                    .iter()
+                    .any(|accepted| syllable::normalize(accepted) == answer)
+                    .into()
+            }
+            (Input::English { accepted }, Response::Text(answer)) => accepted
+                .iter()
+                .any(|accepted| gloss::matches(accepted, answer))
+                .into(),
+            (Input::Vietnamese { orders, dialect }, Response::Text(answer)) => {
+                if orders
+                    .iter()
+                    .any(|order| answer::check_sentence(order, answer, *dialect))
+                {
+                    Grade::Correct
+                } else if orders
+                    .iter()
+                    .any(|order| answer::check_sentence_without_tones(order, answer, *dialect))
+                {
+                    Grade::AlmostCorrect
+                } else {
+                    Grade::Wrong
+                }
+            }
+            (Input::Choice { correct, .. }, Response::Choice(index)) => (correct == index).into(),
+            (Input::Order { words, .. }, Response::Order(order)) => {
+                // Every word must be used exactly once
+                let mut sorted = order.clone();
+                sorted.sort_unstable();
+                if sorted != (0..words.len()).collect::<Vec<_>>() {
+                    return Grade::Wrong;
+                }
+
+                // Any order that forms a sentence is correct
+                let ordered = order
+                    .iter()
+                    .map(|index| words[*index].clone())
+                    .collect::<Vec<_>>();
+                Sentence::parse(&ordered, vec![]).is_some().into()
+            }
+            _ => Grade::Wrong,
+        }
+    }
+
+    /// The response as it's shown to the learner.
+    pub fn response_text(&self, response: &Response) -> String {
+        match (&self.input, response) {
+            (_, Response::Text(answer)) => answer.clone(),
+            (Input::Choice { options, .. }, Response::Choice(index)) => {
+                options.get(*index).cloned().unwrap_or_default()
+            }
+            (Input::Order { words, .. }, Response::Order(order)) => order
+                .iter()
+                .filter_map(|index| words.get(*index).map(|word| word.to_string()))
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => String::new(),
+        }
+    }
+
+    /// The correct answer, shown after answering.
+    ///
+    /// Indices that are out of range are left out, like in the response.
+    pub fn answer(&self) -> String {
+        match &self.input {
+            Input::Text { accepted } | Input::English { accepted } => {
+                accepted.first().cloned().unwrap_or_default()
+            }
+            Input::Vietnamese { orders, .. } => orders
+                .first()
+                .map(|order| {
+                    order
+                        .iter()
+                        .map(|word| word.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default(),
+            Input::Choice { options, correct } => {
+                options.get(*correct).cloned().unwrap_or_default()
+            }
+            Input::Order { words, expected } => expected
+                .iter()
+                .filter_map(|index| words.get(*index).map(|word| word.to_string()))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// How the learner answers an exercise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    /// Type the answer, which must be one of the accepted answers.
+    Text {
+        /// The accepted answers, the case, spacing, hyphens and punctuation around the
+        /// syllables are ignored.
+        accepted: Vec<String>,
+    },
+    /// Type an English translation, compared leniently with the accepted translations.
+    English {
+        /// The accepted translations.
+        accepted: Vec<String>,
+    },
+    /// Type a Vietnamese sentence, the words can be in any of the grammatical orders.
+    Vietnamese {
+        /// The words in every order that is grammatical.
+        orders: Vec<Vec<Word>>,
+        /// The dialect the answer must be in, any dialect when it's `None`.
+        dialect: Option<Dialect>,
+    },
+    /// Pick one of the options.
+    Choice {
+        /// The texts to pick from.
+        options: Vec<String>,
+        /// Index of the right option.
+        correct: usize,
+    },
+    /// Put the words in order, `expected` is the order of the generated sentence.
+    Order {
+        /// The words of the sentence, shuffled.
+        words: Vec<Word>,
+        /// Indices of the words in the right order.
+        expected: Vec<usize>,
+    },
+}
+
+/// What the learner answered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Response {
+    /// Typed text.
+    Text(String),
+    /// Index of the picked option.
+    Choice(usize),
+    /// Indices of the words in the chosen order.
+    Order(Vec<usize>),
+}
+
+/// How well an exercise was answered.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Grade {
+    /// Right, including the tone marks.
+    Correct,
+    /// Only the tone marks are wrong.
+    AlmostCorrect,
+    /// Anything else.
+    Wrong,
+}
+
+impl From<bool> for Grade {
+    fn from(correct: bool) -> Self {
+        if correct {
+            Grade::Correct
+        } else {
+            Grade::Wrong
+        }
+    }
+}
+
+/// The results of a practice session.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Summary {
+    /// How many exercises were answered.
+    pub answered: usize,
+    /// How many exercises were answered correctly.
+    pub correct: usize,
+}
+
+impl Summary {
+    /// Add the result of an exercise.
+    pub fn record(&mut self, correct: bool) {
+        self.answered += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+
+    /// Percentage of correct answers.
+    pub fn accuracy(&self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            self.correct as f64 / self.answered as f64 * 100.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::etymology::Etymology;
+    use anyhow::Result;
+
+    #[test]
+    fn test_exercises() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let settings = Settings::default();
+        let schedule = Schedule::default();
+
+        for drill in Drill::all() {
+            for _ in 0..20 {
+                let exercise = drill.exercise(&mut rng, &settings, &schedule)?;
+
+                // The shown answer must be accepted
+                let response = match &exercise.input {
+                    Input::Text { .. } | Input::English { .. } | Input::Vietnamese { .. } => {
+                        Response::Text(exercise.answer())
+                    }
+                    Input::Choice { correct, .. } => Response::Choice(*correct),
+                    Input::Order { expected, .. } => Response::Order(expected.clone()),
+                };
+                assert_eq!(exercise.grade(&response), Grade::Correct);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_origin() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let origin = Etymology::Native;
+        let settings = Settings {
+            origin: Some(origin.clone()),
+            ..Settings::default()
+        };
+        let schedule = Schedule::default();
+
+        // Every exercise practices a word of the origin
+        for drill in Drill::all() {
+            for _ in 0..10 {
+                let exercise = drill.exercise(&mut rng, &settings, &schedule)?;
+                assert!(
+                    exercise.words.iter().any(|word| word.has_origin(&origin)),
+                    "{}: {:?}",
+                    drill,
+                    exercise.words
+                );
+            }
+        }
+
+        // There are no English adjectives to reduplicate
+        let settings = Settings {
+            origin: Some(Etymology::English),
+            ..Settings::default()
+        };
+        assert!(Drill::Reduplication
+            .exercise(&mut rng, &settings, &schedule)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_order() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let exercise =
+            Drill::Scramble.exercise(&mut rng, &Settings::default(), &Schedule::default())?;
+
+        if let Input::Order { words, .. } = &exercise.input {
+            // Words can't be left out or used twice
+            assert_eq!(exercise.grade(&Response::Order(vec![])), Grade::Wrong);
+            assert_eq!(
+                exercise.grade(&Response::Order(vec![0; words.len()])),
+                Grade::Wrong
+            );
+        } else {
+            panic!("Scramble exercise should be ordered");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer() {
+        let exercise = |input| Exercise {
+            prompt: String::new(),
+            input,
+            explanation: String::new(),
+            translation: None,
+            words: vec![],
+        };
+
+        // Indices out of range don't panic
+        let choice = exercise(Input::Choice {
+            options: vec!["cái".to_string()],
+            correct: 1,
+        });
+        assert_eq!(choice.answer(), "");
+        let order = exercise(Input::Order {
+            words: Word::defaults().take(1).collect(),
+            expected: vec![1, 0],
+        });
+        assert_eq!(order.answer(), Word::defaults().next().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_translation() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let settings = Settings::default();
+        let schedule = Schedule::default();
+
+        let exercise = Drill::ToEnglish.exercise(&mut rng, &settings, &schedule)?;
+        let answer = exercise.answer().to_lowercase().replace('.', "");
+        assert_eq!(exercise.grade(&Response::Text(answer)), Grade::Correct);
+
+        // Only getting the tones wrong is almost correct
+        let exercise = Drill::ToVietnamese.exercise(&mut rng, &settings, &schedule)?;
+        let answer = syllable::strip_tones(&syllable::normalize(&exercise.answer()));
+        if syllable::normalize(&exercise.answer()) != answer {
+            assert_eq!(
+                exercise.grade(&Response::Text(answer)),
+                Grade::AlmostCorrect
+            );
+        }
+        assert_eq!(
+            exercise.grade(&Response::Text("không".to_string())),
+            Grade::Wrong
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut summary = Summary::default();
+        assert_eq!(summary.accuracy(), 0.0);
+
+        summary.record(true);
+        summary.record(false);
+        assert_eq!(summary.answered, 2);
+        assert_eq!(summary.correct, 1);
+        assert_eq!(summary.accuracy(), 50.0);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    drill::{choice, Exercise, Input},
+    grammar::{
+        reduplication::Reduplication,
+        word::{Adjective, Class, Word},
+    },
+    settings::Settings,
+    srs::{Item, Schedule},
+};
+use anyhow::{anyhow, Result};
+use rand::Rng;
+
+/// Ask to either form or interpret a reduplicated adjective.
+pub fn exercise<R>(rng: &mut R, settings: &Settings, schedule: &Schedule) -> Result<Exercise>
+where
+    R: Rng,
+{
+    // Pick a random reduplication of a random adjective
+    let candidates = Word::defaults()
+        .filter(|word| {
+            *word.class() == Class::Adjective(Adjective::default()) && settings.allows(word)
+        })
+        .flat_map(|word| {
+            [Reduplication::Softened, Reduplication::Intensified]
+                .iter()
+                .filter(|reduplication| reduplication.applies_to(&word))
+                .map(|reduplication| (word.clone(), *reduplication))
+                .collect::<Vec<_>>()
+        });
+    let (base, reduplication) = schedule
+        .choose(rng, candidates, |(word, _)| vec![Item::Word(word.id())])
+        .ok_or_else(|| anyhow!("Could not find an adjective that can be reduplicated"))?;
+
+    let word = base.in_dialect(settings.dialect);
+    let reduplicated = reduplication.apply(&word)?;
+    let meaning = reduplication.meaning(word.meaning());
+    let explanation = explanation(reduplication);
+
+    // Also accept the reduplication of the word in other dialects if allowed
+    let mut accepted = vec![reduplicated.clone()];
+    for dialect in settings.answer_dialects() {
+        let variant = reduplication.apply(&base.in_dialect(dialect))?;
+        if !accepted.contains(&variant) {
+            accepted.push(variant);
+        }
+    }
+
+    if rng.gen() {
+        // Form the reduplication
+        Ok(Exercise {
+            prompt: format!(
+                "Say \"{}\" by reduplicating {} ({}):",
+                meaning,
+                word,
+                word.meaning()
+            ),
+            input: Input::Text { accepted },
+            explanation,
+            translation: Some(format!("{}: {}", reduplicated, meaning)),
+            words: vec![word],
+        })
+    } else {
+        // Interpret the reduplication
+        let distractors = [Reduplication::Softened, Reduplication::Intensified]
+            .iter()
+            .map(|other| other.meaning(word.meaning()))
+            .chain(std::iter::once(format!("not {}", word.meaning())));
+        let input = choice::options(rng, meaning, distractors);
+
+        Ok(Exercise {
+            prompt: format!("What does \"{}\" mean?", reduplicated),
+            input,
+            explanation,
+            translation: Some(format!("{}: {}", word, word.meaning())),
+            words: vec![word],
+        })
+    }
+}
+
+/// Explain the rule for the reduplication.
+fn explanation(reduplication: Reduplication) -> String {
+    match reduplication {
+        Reduplication::Softened => "Repeating an adjective in front softens it. The repeated \
+            syllable gets the even tone of the same register: sắc and hỏi become ngang, nặng and \
+            ngã become huyền. A final p, t, c or ch becomes m, n, ng or nh: đỏ → đo đỏ, \
+            mát → man mát."
+            .to_string(),
+        Reduplication::Intensified => "Repeating an adjective with a final stop twice after \
+            it intensifies it. The repetitions end in a nasal and get the huyền and ngang tones: \
+            sạch → sạch sành sanh."
+            .to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    drill::{Exercise, Input},
+    grammar::{gloss, sentence::Sentence, Generate},
+    settings::Settings,
+    srs::Schedule,
+};
+use anyhow::{anyhow, Result};
+use rand::{seq::SliceRandom, Rng};
+
+/// Ask to put the shuffled words of a sentence in the right order.
+pub fn exercise<R>(rng: &mut R, settings: &Settings, schedule: &Schedule) -> Result<Exercise>
+where
+    R: Rng,
+{
+    let words = schedule.sentence(rng, settings)?;
+    let derivation = Sentence::parse(&words, vec![])
+        .ok_or_else(|| anyhow!("Generated sentence doesn't follow any structure"))?;
+
+    // Shuffle the words, making sure they are not already in the right order
+    let mut order = (0..words.len()).collect::<Vec<_>>();
+    while words.len() > 1 && order.windows(2).all(|pair| pair[0] < pair[1]) {
+        order.shuffle(rng);
+    }
+    let shuffled = order.iter().map(|index| words[*index].clone()).collect();
+
+    // Where the words in the right order are in the shuffled list
+    let expected = (0..words.len())
+        .map(|index| order.iter().position(|shuffled| *shuffled == index))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| anyhow!("Shuffled words are missing a word"))?;
+
+    let structures = derivation
+        .structures()
+        .iter()
+        .map(|structure| structure.to_string())
+        .collect::<Vec<_>>();
+    let explanation = match structures.split_first() {
+        Some((sentence, [])) => format!("The sentence follows the structure {}.", sentence),
+        Some((sentence, [parts @ .., last])) => format!(
+            "The sentence follows the structure {}, which is made of {}.",
+            sentence,
+            if parts.is_empty() {
+                last.to_string()
+            } else {
+                format!("{} and {}", parts.join(", "), last)
+            }
+        ),
+        None => String::new(),
+    };
+
+    Ok(Exercise {
+        prompt: "Put the words in the right order:".to_string(),
+        input: Input::Order {
+            words: shuffled,
+            expected,
+        },
+        explanation,
+        translation: Some(gloss::gloss(&words)),
+        words,
+    })
+}
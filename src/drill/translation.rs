@@ -0,0 +1,179 @@
+use crate::{
+    drill::{Exercise, Input},
+    grammar::{gloss, sentence::Sentence, word::Word, Derivation, Generate},
+    settings::Settings,
+    srs::Schedule,
+};
+use anyhow::Result;
+use rand::Rng;
+use std::{iter, ops::Range};
+
+/// The most noun phrases that are swapped to find the other grammatical orders.
+const MAX_PHRASES: usize = 4;
+
+/// Ask to translate a generated Vietnamese sentence to English.
+pub fn to_english<R>(rng: &mut R, settings: &Settings, schedule: &Schedule) -> Result<Exercise>
+where
+    R: Rng,
+{
+    let words = schedule.sentence(rng, settings)?;
+    let sentence = words
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let translation = gloss::gloss(&words);
+
+    Ok(Exercise {
+        prompt: format!("Translate to English: {}", sentence),
+        input: Input::English {
+            accepted: gloss::glosses(&words),
+        },
+        explanation: meanings(&words),
+        translation: Some(translation),
+        words,
+    })
+}
+
+/// Ask to translate the English gloss of a generated sentence to Vietnamese.
+pub fn to_vietnamese<R>(rng: &mut R, settings: &Settings, schedule: &Schedule) -> Result<Exercise>
+where
+    R: Rng,
+{
+    let words = schedule.sentence(rng, settings)?;
+    let translation = gloss::gloss(&words);
+
+    let orders = orders(&words);
+
+    Ok(Exercise {
+        prompt: format!("Translate to Vietnamese: {}", translation),
+        input: Input::Vietnamese {
+            orders,
+            dialect: settings.answer_dialect(),
+        },
+        explanation: meanings(&words),
+        translation: Some(translation),
+        words,
+    })
+}
+
+/// List the meaning of every word except names, "đọc: read".
+fn meanings(words: &[Word]) -> String {
+    words
+        .iter()
+        .filter(|word| !word.meaning().is_empty() && !word.is_person())
+        .map(|word| format!("{}: {}", word, word.meaning()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The grammatical orders of the words, the noun phrases can trade places when they fit there.
+///
+/// The generated order comes first so it's shown as the answer.
+fn orders(words: &[Word]) -> Vec<Vec<Word>> {
+    let mut orders = vec![words.to_vec()];
+    let phrases = match Sentence::parse(words, vec![]) {
+        Some(derivation) => noun_phrases(&derivation, 0),
+        None => return orders,
+    };
+    if phrases.len() > MAX_PHRASES {
+        return orders;
+    }
+
+    for swapped in permutations(&phrases) {
+        // Put the phrases in the places of the phrases in the sentence
+        let mut order = vec![];
+        let mut end = 0;
+        for (place, phrase) in phrases.iter().zip(swapped) {
+            order.extend_from_slice(&words[end..place.start]);
+            order.extend_from_slice(&words[phrase]);
+            end = place.end;
+        }
+        order.extend_from_slice(&words[end..]);
+
+        // The same phrase twice gives the same order
+        if !orders.contains(&order) && Sentence::parse(&order, vec![]).is_some() {
+            orders.push(order);
+        }
+    }
+
+    orders
+}
+
+/// Where the noun phrases of a derivation are in the words, in the order of the sentence.
+fn noun_phrases(derivation: &Derivation, start: usize) -> Vec<Range<usize>> {
+    let start = start + derivation.start;
+    if derivation.source == "noun_phrases.txt" {
+        return iter::once(start..start + derivation.len).collect();
+    }
+
+    derivation
+        .children
+        .iter()
+        .flat_map(|child| noun_phrases(child, start))
+        .collect()
+}
+
+/// All orders the items can be put in.
+fn permutations<T>(items: &[T]) -> Vec<Vec<T>>
+where
+    T: Clone,
+{
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    (0..items.len())
+        .flat_map(|index| {
+            let mut rest = items.to_vec();
+            let first = rest.remove(index);
+            permutations(&rest).into_iter().map(move |mut order| {
+                order.insert(0, first.clone());
+                order
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_orders() -> Result<()> {
+        let nouns = Word::parse_file(
+            "common_nouns.txt",
+            "mèo(SUBJECT + OBJECT): cat\nchó(SUBJECT + OBJECT): dog",
+        )?;
+        let verbs = Word::parse_file("verbs.txt", "thấy: see")?;
+        let (cat, dog, see) = (&nouns[0], &nouns[1], &verbs[0]);
+
+        // The subject and object trade places
+        let words = vec![cat.clone(), see.clone(), dog.clone()];
+        assert_eq!(
+            orders(&words),
+            vec![words, vec![dog.clone(), see.clone(), cat.clone()]]
+        );
+
+        // Swapping the same phrase doesn't give another order
+        let words = vec![cat.clone(), see.clone(), cat.clone()];
+        assert_eq!(orders(&words), vec![words]);
+
+        // Every order is grammatical and found by trying every order of the words
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let words = Sentence::generate(&mut rng, vec![])?.collect::<Vec<_>>();
+            let orders = orders(&words);
+            assert_eq!(orders[0], words);
+            if words.len() <= 6 {
+                let all = permutations(&words);
+                assert!(orders
+                    .iter()
+                    .all(|order| all.contains(order) && Sentence::parse(order, vec![]).is_some()));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,364 @@
+//! Export words and generated sentences as Anki cards and printable worksheets.
+use crate::grammar::{dialect::Dialect, record::Record, word::Word};
+use anyhow::Result;
+use std::io::Write;
+
+/// Added to all cards, to find them back in Anki.
+const TAG: &str = "tieng-viet";
+
+/// A note with a question on the front and the answer on the back.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Card {
+    /// The question, a word or sentence in Vietnamese.
+    pub front: String,
+    /// The meaning or translation.
+    pub back: String,
+    /// How the front is pronounced in IPA, shown on the back.
+    pub pronunciation: String,
+    /// Anki tags can't contain spaces.
+    pub tags: Vec<String>,
+}
+
+impl Card {
+    /// The word with its meaning and pronunciation in the dialect, tagged with its class like
+    /// "common_noun".
+    ///
+    /// **Sách**: book, sajk˧˥, tags tieng-viet and common_noun.
+    pub fn word(word: &Word, dialect: Dialect) -> Result<Self> {
+        Ok(Card {
+            front: word.to_string(),
+            back: word.meaning().to_string(),
+            pronunciation: word.pronunciation(dialect)?,
+            tags: vec![TAG.to_string(), tag(&word.class().to_string())],
+        })
+    }
+
+    /// The sentence with its translation, tagged with the classes of the words in it.
+    pub fn sentence(record: &Record) -> Self {
+        let mut tags = vec![TAG.to_string(), "sentence".to_string()];
+        for word in &record.words {
+            let class = tag(&word.class().to_string());
+            if !tags.contains(&class) {
+                tags.push(class);
+            }
+        }
+
+        Card {
+            front: record.sentence.clone(),
+            back: record.translation.clone(),
+            pronunciation: record.pronunciations.join(" "),
+            tags,
+        }
+    }
+}
+
+/// Write the cards as CSV that Anki imports as "Basic" notes in the deck, with "File > Import".
+///
+/// The lines starting with '#' tell Anki how to import the file. The pronunciation is put on the
+/// back, a "Basic" note has no field for it.
+///
+/// See: <https://docs.ankiweb.net/importing/text-files.html#file-headers>
+pub fn write_anki_csv<W>(mut writer: W, cards: &[Card], deck: &str) -> Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "#separator:Comma")?;
+    writeln!(writer, "#html:false")?;
+    writeln!(writer, "#notetype:Basic")?;
+    writeln!(writer, "#deck:{}", deck)?;
+    writeln!(writer, "#columns:Front,Back,Tags")?;
+    writeln!(writer, "#tags column:3")?;
+
+    let mut csv = csv::Writer::from_writer(writer);
+    for card in cards {
+        let back = if card.pronunciation.is_empty() {
+            card.back.clone()
+        } else {
+            format!("{} [{}]", card.back, card.pronunciation)
+        };
+        csv.write_record([&card.front, &back, &card.tags.join(" ")])?;
+    }
+    csv.flush()?;
+
+    Ok(())
+}
+
+/// Exercises for in the classroom, with the answers at the end.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Worksheet {
+    /// Shown at the top of the worksheet.
+    pub title: String,
+    /// What the students should do, shown under the title.
+    pub instructions: String,
+    /// The questions with their answers.
+    pub items: Vec<(String, String)>,
+}
+
+impl Worksheet {
+    /// Translate the words to English, the answers have the pronunciation in the dialect.
+    pub fn words(title: &str, words: &[Word], dialect: Dialect) -> Result<Self> {
+        Ok(Worksheet {
+            title: title.to_string(),
+            instructions: "Translate the words to English.".to_string(),
+            items: words
+                .iter()
+                .map(|word| {
+                    Ok((
+                        word.to_string(),
+                        format!(
+                            "{} ({}) [{}]",
+                            word.meaning(),
+                            word.class(),
+                            word.pronunciation(dialect)?
+                        ),
+                    ))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Translate the sentences to English, the answers have the pronunciation.
+    pub fn sentences(title: &str, records: &[Record]) -> Self {
+        Worksheet {
+            title: title.to_string(),
+            instructions: "Translate the sentences to English.".to_string(),
+            items: records
+                .iter()
+                .map(|record| {
+                    (
+                        record.sentence.clone(),
+                        format!(
+                            "{} [{}]",
+                            record.translation,
+                            record.pronunciations.join(" ")
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Write the worksheet as Markdown, the answers are under their own heading.
+    pub fn write_markdown<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "# {}\n", escape_markdown(&self.title))?;
+        writeln!(writer, "{}\n", escape_markdown(&self.instructions))?;
+        for (index, (question, _)) in self.items.iter().enumerate() {
+            writeln!(writer, "{}. {}", index + 1, escape_markdown(question))?;
+        }
+
+        writeln!(writer, "\n## Answers\n")?;
+        for (index, (_, answer)) in self.items.iter().enumerate() {
+            writeln!(writer, "{}. {}", index + 1, escape_markdown(answer))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the worksheet as a HTML page, the answers are printed on their own page.
+    pub fn write_html<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        let list = |items: Vec<&String>| {
+            items
+                .into_iter()
+                .map(|item| format!("<li>{}</li>\n", escape_html(item)))
+                .collect::<String>()
+        };
+
+        write!(
+            writer,
+            "<!DOCTYPE html>\n\
+             <html lang=\"vi\">\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n\
+             <style>.answers {{ page-break-before: always; }} li {{ margin: 1em 0; }}</style>\n\
+             </head>\n\
+             <body>\n\
+             <h1>{title}</h1>\n\
+             <p>{instructions}</p>\n\
+             <ol>\n{questions}</ol>\n\
+             <section class=\"answers\">\n\
+             <h2>Answers</h2>\n\
+             <ol>\n{answers}</ol>\n\
+             </section>\n\
+             </body>\n\
+             </html>\n",
+            title = escape_html(&self.title),
+            instructions = escape_html(&self.instructions),
+            questions = list(self.items.iter().map(|(question, _)| question).collect()),
+            answers = list(self.items.iter().map(|(_, answer)| answer).collect()),
+        )?;
+
+        Ok(())
+    }
+
+    /// Write the worksheet as a LaTeX document, the answers are printed on their own page.
+    ///
+    /// It's built with `xelatex` or `lualatex`, the Charis SIL font has both the Vietnamese
+    /// letters and the IPA of the pronunciation.
+    pub fn write_latex<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        let list = |items: Vec<&String>| {
+            items
+                .into_iter()
+                .map(|item| format!("  \\item {}\n", escape_latex(item)))
+                .collect::<String>()
+        };
+
+        write!(
+            writer,
+            "\\documentclass{{article}}\n\
+             \\usepackage{{fontspec}}\n\
+             \\setmainfont{{Charis SIL}}\n\
+             \\begin{{document}}\n\
+             \\section*{{{title}}}\n\
+             {instructions}\n\
+             \\begin{{enumerate}}\n{questions}\\end{{enumerate}}\n\
+             \\newpage\n\
+             \\section*{{Answers}}\n\
+             \\begin{{enumerate}}\n{answers}\\end{{enumerate}}\n\
+             \\end{{document}}\n",
+            title = escape_latex(&self.title),
+            instructions = escape_latex(&self.instructions),
+            questions = list(self.items.iter().map(|(question, _)| question).collect()),
+            answers = list(self.items.iter().map(|(_, answer)| answer).collect()),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Anki tags are separated by spaces, "common noun" becomes "common_noun".
+fn tag(name: &str) -> String {
+    name.replace(' ', "_")
+}
+
+/// Characters that would be read as formatting are escaped with a backslash.
+fn escape_markdown(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| {
+            let escaped = "\\`*_[]<>#|".contains(c);
+            escaped.then_some('\\').into_iter().chain(Some(c))
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_latex(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' => "\\textbackslash{}".to_string(),
+            '~' => "\\textasciitilde{}".to_string(),
+            '^' => "\\textasciicircum{}".to_string(),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grammar::record, settings::Settings};
+    use anyhow::{anyhow, Result};
+    use indoc::indoc;
+
+    #[test]
+    fn test_anki_csv() -> Result<()> {
+        let book = Word::defaults()
+            .find(|word| word.to_string() == "sách")
+            .ok_or_else(|| anyhow!("Word not found"))?;
+        let records = record::generate(1, 1, None, &Settings::default())?;
+        let cards = [
+            Card::word(&book, Dialect::Northern)?,
+            Card::sentence(&records[0]),
+        ];
+        assert!(cards[1].tags.contains(&"sentence".to_string()));
+        assert_eq!(cards[1].pronunciation, records[0].pronunciations.join(" "));
+
+        let mut output = vec![];
+        write_anki_csv(&mut output, &cards[..1], "Tiếng Việt")?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            indoc! {"
+                #separator:Comma
+                #html:false
+                #notetype:Basic
+                #deck:Tiếng Việt
+                #columns:Front,Back,Tags
+                #tags column:3
+                sách,book [{}],tieng-viet common_noun
+            "}
+            .replace("{}", &book.pronunciation(Dialect::Northern)?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_worksheet() -> Result<()> {
+        let worksheet = Worksheet {
+            title: "Lesson #1".to_string(),
+            instructions: "Translate the sentences to English.".to_string(),
+            items: vec![("Tôi đọc sách".to_string(), "I read <the> book_".to_string())],
+        };
+
+        let mut markdown = vec![];
+        worksheet.write_markdown(&mut markdown)?;
+        assert_eq!(
+            String::from_utf8(markdown)?,
+            indoc! {r"
+                # Lesson \#1
+
+                Translate the sentences to English.
+
+                1. Tôi đọc sách
+
+                ## Answers
+
+                1. I read \<the\> book\_
+            "}
+        );
+
+        let mut html = vec![];
+        worksheet.write_html(&mut html)?;
+        let html = String::from_utf8(html)?;
+        assert!(html.contains("<title>Lesson #1</title>"));
+        assert!(html.contains("<li>I read &lt;the&gt; book_</li>"));
+
+        // The answers of the words have the pronunciation
+        let book = Word::defaults()
+            .find(|word| word.to_string() == "sách")
+            .ok_or_else(|| anyhow!("Word not found"))?;
+        let words = Worksheet::words("Words", std::slice::from_ref(&book), Dialect::Southern)?;
+        assert_eq!(
+            words.items[0].1,
+            format!(
+                "book (common noun) [{}]",
+                book.pronunciation(Dialect::Southern)?
+            )
+        );
+
+        let mut latex = vec![];
+        worksheet.write_latex(&mut latex)?;
+        let latex = String::from_utf8(latex)?;
+        assert!(latex.contains("\\section*{Lesson \\#1}"));
+        assert!(latex.contains("  \\item Tôi đọc sách\n"));
+        assert!(latex.contains("  \\item I read <the> book\\_\n"));
+
+        Ok(())
+    }
+}
@@ -1,16 +1,34 @@
+//! The line format of the word lists and structure files.
+
+use anyhow::{anyhow, Result};
+
+/// The metadata of a word line, keys with optional values.
+pub type Metadata<'a> = Vec<(&'a str, Option<&'a str>)>;
+
 /// Parse a file consisting of lines.
 pub fn parse_str(data: &str) -> impl Iterator<Item = &str> {
+    parse_lines(data).map(|(_, line)| line)
+}
+
+/// Parse a file consisting of lines, with the line numbers starting at 1.
+pub fn parse_lines(data: &str) -> impl Iterator<Item = (usize, &str)> {
     data.lines()
+        .enumerate()
         // Remove comments at the end of the line
-        .map(|line| line.split_once("#").map(|(data, _)| data).unwrap_or(line))
+        .map(|(index, line)| {
+            (
+                index + 1,
+                line.split_once("#").map(|(data, _)| data).unwrap_or(line),
+            )
+        })
         // Trim whitespace of lines
-        .map(|line| line.trim())
+        .map(|(number, line)| (number, line.trim()))
         // Remove empty lines & comments
-        .filter(|line| !line.is_empty() && !line.starts_with("#"))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("#"))
 }
 
 /// Parse a line with the format "word(metadata): description"
-pub fn parse_word_line(line: &str) -> (&str, Vec<(&str, Option<&str>)>, Option<&str>) {
+pub fn parse_word_line(line: &str) -> (&str, Metadata<'_>, Option<&str>) {
     // Extract the description part :
     let (word_with_metadata, description) = line
         .split_once(":")
@@ -28,7 +46,6 @@ pub fn parse_word_line(line: &str) -> (&str, Vec<(&str, Option<&str>)>, Option<&
                 metadata
                     .trim_end_matches(metadata_trim)
                     .split("+")
-                    .into_iter()
                     .map(|metadata| {
                         // Return the data behind "=" if applicable
                         if let Some((word, data)) = metadata.split_once("=") {
@@ -45,6 +62,44 @@ pub fn parse_word_line(line: &str) -> (&str, Vec<(&str, Option<&str>)>, Option<&
     (word.trim(), metadata, description)
 }
 
+/// Write a line with the format "word(metadata): description", the reverse of
+/// [`parse_word_line`].
+///
+/// Fails when a part contains characters that would be read differently, like a '#' in the
+/// description which starts a comment.
+pub fn write_word_line(
+    word: &str,
+    metadata: &Metadata,
+    description: Option<&str>,
+) -> Result<String> {
+    fn check<'a>(part: &'a str, reserved: &str) -> Result<&'a str> {
+        match part.chars().find(|c| reserved.contains(*c)) {
+            Some(c) => Err(anyhow!("\"{}\" can't contain '{}' in a word line", part, c)),
+            None => Ok(part),
+        }
+    }
+
+    let mut line = check(word, ":(#")?.to_string();
+    if !metadata.is_empty() {
+        let metadata = metadata
+            .iter()
+            .map(|(key, data)| {
+                let key = check(key, ":()+=#")?;
+                Ok(match data {
+                    Some(data) => format!("{} = {}", key, check(data, ":()+=#")?),
+                    None => key.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        line.push_str(&format!("({})", metadata.join(" + ")));
+    }
+    if let Some(description) = description {
+        line.push_str(&format!(": {}", check(description, "#")?));
+    }
+
+    Ok(line)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,6 +117,9 @@ mod tests {
         assert_eq!(iter.next(), Some("A line with data"));
         assert_eq!(iter.next(), Some("Data + comment"));
         assert_eq!(iter.next(), None);
+
+        let lines = parse_lines("# A comment\n\nA line with data").collect::<Vec<_>>();
+        assert_eq!(lines, vec![(3, "A line with data")]);
     }
 
     #[test]
@@ -95,4 +153,17 @@ mod tests {
         );
         assert!(description.is_none());
     }
+
+    #[test]
+    fn test_write_word_line() -> Result<()> {
+        let line = "bát(SOUTHERN = chén + OBJECT): bowl: for rice";
+        let (word, metadata, description) = parse_word_line(line);
+        assert_eq!(write_word_line(word, &metadata, description)?, line);
+        assert_eq!(write_word_line("bát", &vec![], None)?, "bát");
+
+        assert!(write_word_line("bát", &vec![], Some("bowl #1")).is_err());
+        assert!(write_word_line("bát", &vec![("PAST", Some("a + b"))], None).is_err());
+
+        Ok(())
+    }
 }
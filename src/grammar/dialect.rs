@@ -0,0 +1,53 @@
+//! The regional varieties and how their words differ.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// Regional variety of Vietnamese.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_language#Dialects>
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Dialect {
+    /// Hà Nội, the standard spoken in the north.
+    #[default]
+    Northern,
+    /// Sài Gòn, the standard spoken in the south.
+    Southern,
+}
+
+impl Dialect {
+    /// All dialects.
+    pub fn all() -> [Dialect; 2] {
+        [Dialect::Northern, Dialect::Southern]
+    }
+
+    /// The opposite dialect.
+    pub fn other(self) -> Dialect {
+        match self {
+            Dialect::Northern => Dialect::Southern,
+            Dialect::Southern => Dialect::Northern,
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = anyhow::Error;
+
+    fn from_str(dialect: &str) -> Result<Self> {
+        match dialect.to_lowercase().as_str() {
+            "northern" | "north" | "hanoi" | "hà nội" => Ok(Dialect::Northern),
+            "southern" | "south" | "saigon" | "sài gòn" => Ok(Dialect::Southern),
+            _ => bail!("Unrecognized dialect {}", dialect),
+        }
+    }
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dialect::Northern => write!(f, "Northern (Hà Nội)"),
+            Dialect::Southern => write!(f, "Southern (Sài Gòn)"),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Where the words came from.
+
+use crate::grammar::defaults_parser::Metadata;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// Where a word originally came from.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_language#Vocabulary>
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Etymology {
+    /// Hán-Việt, borrowed from Chinese, with the Chinese character if known.
+    ///
+    /// **Quốc** (國): Country, as in **quốc** gia.
+    SinoVietnamese(Option<String>),
+    /// Thuần Việt, not borrowed.
+    ///
+    /// **Nước**: Country, water.
+    Native,
+    /// Borrowed from French.
+    ///
+    /// **Bơ** (beurre): Butter.
+    French,
+    /// Borrowed from English.
+    ///
+    /// **In-tơ-nét**: Internet.
+    English,
+}
+
+impl Etymology {
+    /// All origins, the Chinese character is left empty.
+    pub fn all() -> [Etymology; 4] {
+        [
+            Etymology::SinoVietnamese(None),
+            Etymology::Native,
+            Etymology::French,
+            Etymology::English,
+        ]
+    }
+
+    /// Find the etymology in the metadata of a word line.
+    ///
+    /// Allowed properties: SINO: *Chinese character*, NATIVE, FRENCH, ENGLISH.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Etymology> {
+        metadata.iter().find_map(|(key, data)| match *key {
+            "SINO" => Some(Etymology::SinoVietnamese(data.map(|data| data.to_string()))),
+            "NATIVE" => Some(Etymology::Native),
+            "FRENCH" => Some(Etymology::French),
+            "ENGLISH" => Some(Etymology::English),
+            _ => None,
+        })
+    }
+
+    /// Whether both have the same origin, regardless of the Chinese character.
+    pub fn same_origin(&self, other: &Etymology) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl FromStr for Etymology {
+    type Err = anyhow::Error;
+
+    /// Parse the name of an origin like "sino" or "french", without the Chinese character.
+    fn from_str(origin: &str) -> Result<Self> {
+        match origin.trim().to_lowercase().as_str() {
+            "sino" | "sino-vietnamese" | "hán-việt" | "hán việt" => {
+                Ok(Etymology::SinoVietnamese(None))
+            }
+            "native" | "thuần việt" => Ok(Etymology::Native),
+            "french" => Ok(Etymology::French),
+            "english" => Ok(Etymology::English),
+            _ => bail!("Unrecognized origin {}", origin),
+        }
+    }
+}
+
+impl Display for Etymology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Etymology::SinoVietnamese(Some(character)) => write!(f, "Hán-Việt ({})", character),
+            Etymology::SinoVietnamese(None) => write!(f, "Hán-Việt"),
+            Etymology::Native => write!(f, "Thuần Việt (native)"),
+            Etymology::French => write!(f, "French loanword"),
+            Etymology::English => write!(f, "English loanword"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::defaults_parser::parse_word_line;
+
+    #[test]
+    fn test_from_metadata() {
+        let (_, metadata, _) = parse_word_line("quốc(SINO = 國): country");
+        assert_eq!(
+            Etymology::from_metadata(&metadata),
+            Some(Etymology::SinoVietnamese(Some("國".to_string())))
+        );
+
+        let (_, metadata, _) = parse_word_line("bơ(ITEM + FRENCH): butter");
+        assert_eq!(Etymology::from_metadata(&metadata), Some(Etymology::French));
+
+        let (_, metadata, _) = parse_word_line("bơ(ITEM): butter");
+        assert_eq!(Etymology::from_metadata(&metadata), None);
+
+        assert!(Etymology::SinoVietnamese(Some("國".to_string()))
+            .same_origin(&Etymology::SinoVietnamese(None)));
+    }
+}
@@ -0,0 +1,293 @@
+//! Rough English translations of sentences and checking translations against them.
+
+use crate::grammar::{
+    lexicon,
+    word::{Class, CommonNoun, CommonNounKind, Tense, Verb, Word},
+};
+
+/// English words that are left out when comparing translations.
+const ARTICLES: [&str; 3] = ["a", "an", "the"];
+
+/// The most translations of a sentence made from the other meanings of its words.
+const MAX_GLOSSES: usize = 64;
+
+/// Compose a rough English translation of a sentence from the meanings of the words.
+///
+/// Classifiers are dropped, countable nouns get an article, demonstratives are put in front
+/// of the noun they point at and verbs are conjugated for the tense marker in front of them.
+///
+/// **Học sinh đọc quyển sách đây**: The student reads this book.
+pub fn gloss(words: &[Word]) -> String {
+    gloss_with(words, &words.iter().map(Word::meaning).collect::<Vec<_>>())
+}
+
+/// The translations of a sentence with every combination of the meanings of its words, the
+/// [`gloss`] with the main meanings first.
+///
+/// **Linh xem phim**: Linh watches the film, Linh sees the film.
+pub fn glosses(words: &[Word]) -> Vec<String> {
+    let mut combinations: Vec<Vec<&str>> = vec![vec![]];
+    for word in words {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                word.meanings().map(move |meaning| {
+                    let mut combination = combination.clone();
+                    combination.push(meaning);
+                    combination
+                })
+            })
+            .take(MAX_GLOSSES)
+            .collect();
+    }
+
+    let mut glosses: Vec<String> = vec![];
+    for meanings in combinations {
+        let gloss = gloss_with(words, &meanings);
+        if !glosses.contains(&gloss) {
+            glosses.push(gloss);
+        }
+    }
+
+    glosses
+}
+
+/// Compose the translation with a meaning for every word.
+fn gloss_with(words: &[Word], meanings: &[&str]) -> String {
+    let mut gloss: Vec<String> = vec![];
+    // Where the last noun phrase starts, to put demonstratives in front of it
+    let mut noun_phrase = 0;
+    let mut after_classifier = false;
+    // Whether a verb already had it's subject
+    let mut has_subject = false;
+    let mut tense = None;
+
+    for (word, meaning) in words.iter().zip(meanings) {
+        match word.class() {
+            // English doesn't have classifiers
+            Class::ClassifierNoun(_) => {
+                noun_phrase = gloss.len();
+                after_classifier = true;
+                continue;
+            }
+            Class::ProperNoun(_) | Class::CommonNoun(_) => {
+                if !after_classifier {
+                    noun_phrase = gloss.len();
+                }
+                if let Class::CommonNoun(CommonNoun {
+                    kind: CommonNounKind::Item,
+                    ..
+                }) = word.class()
+                {
+                    gloss.push("the".to_string());
+                }
+                // Names are not translated
+                if word.is_person() {
+                    gloss.push(word.to_string());
+                } else {
+                    gloss.push(meaning.to_string());
+                }
+                has_subject = true;
+            }
+            Class::Demonstrative(_) => {
+                // "The book this" becomes "this book"
+                if gloss.get(noun_phrase).map(String::as_str) == Some("the") {
+                    gloss.remove(noun_phrase);
+                }
+                gloss.insert(noun_phrase, meaning.to_string());
+            }
+            // The tense is added to the verb
+            Class::TenseMarker(marker) => {
+                tense = Some(*marker);
+                continue;
+            }
+            Class::Verb(Verb { past }) if has_subject => {
+                gloss.push(conjugate(meaning, past.as_deref(), tense))
+            }
+            _ => gloss.push(meaning.to_string()),
+        }
+        after_classifier = false;
+    }
+
+    let gloss = gloss.join(" ");
+    let mut chars = gloss.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => gloss,
+    }
+}
+
+/// Whether an English answer matches the expected translation.
+///
+/// Case, punctuation and articles are ignored, and a word may be given with or without the
+/// plural or third person ending the gloss adds.
+///
+/// **The student reads the book**: student read book.
+pub fn matches(expected: &str, answer: &str) -> bool {
+    let expected = normalize(expected);
+    let answer = normalize(answer);
+
+    expected.len() == answer.len()
+        && expected
+            .iter()
+            .zip(&answer)
+            .all(|(expected, answer)| word_matches(expected, answer))
+}
+
+/// Lowercase English words without articles.
+fn normalize(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty() && !ARTICLES.contains(&word.as_str()))
+        .collect()
+}
+
+/// Whether a word of the answer is the expected word, or the same word with or without an ending.
+///
+/// An answer without the ending must be a word of the lexicon, so "bu" doesn't match "bus".
+fn word_matches(expected: &str, answer: &str) -> bool {
+    expected == answer
+        || inflect(expected) == answer
+        || (inflect(answer) == expected && is_known(answer))
+}
+
+/// Whether a word is used in the meanings of the lexicon.
+fn is_known(word: &str) -> bool {
+    lexicon::words()
+        .iter()
+        .flat_map(Word::meanings)
+        .flat_map(normalize)
+        .any(|known| known == word)
+}
+
+/// Add the ending of the third person or the plural.
+///
+/// **Watch**: watches, **read**: reads.
+fn inflect(word: &str) -> String {
+    if ["s", "sh", "ch", "x", "o"]
+        .iter()
+        .any(|ending| word.ends_with(ending))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// Conjugate the verb for a single subject in a tense.
+///
+/// **Read**: reads, read, is reading, will read.
+fn conjugate(meaning: &str, past: Option<&str>, tense: Option<Tense>) -> String {
+    let (verb, rest) = meaning.split_once(' ').unwrap_or((meaning, ""));
+    let verb = match (verb, tense) {
+        // "Is tall" only changes the "is"
+        ("is", None) | ("is", Some(Tense::Progressive)) => verb.to_string(),
+        ("is", Some(Tense::Past)) => "was".to_string(),
+        ("is", Some(Tense::Future)) => "will be".to_string(),
+        (_, None) => inflect(verb),
+        (_, Some(Tense::Past)) => match past {
+            Some(past) => past.to_string(),
+            None if verb.ends_with('e') => format!("{}d", verb),
+            None => format!("{}ed", verb),
+        },
+        (_, Some(Tense::Progressive)) => match verb.strip_suffix('e') {
+            Some(stem) if !stem.ends_with('e') => format!("is {}ing", stem),
+            _ => format!("is {}ing", verb),
+        },
+        (_, Some(Tense::Future)) => format!("will {}", verb),
+    };
+
+    if rest.is_empty() {
+        verb
+    } else {
+        format!("{} {}", verb, rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Result};
+
+    fn words(contents: &[&str]) -> Result<Vec<Word>> {
+        contents
+            .iter()
+            .map(|content| {
+                Word::defaults()
+                    .find(|word| word.to_string() == *content)
+                    .ok_or_else(|| anyhow!("Word {} not found", content))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gloss() -> Result<()> {
+        assert_eq!(
+            gloss(&words(&["Phúc", "đọc", "sách"])?),
+            "Phúc reads the book."
+        );
+        assert_eq!(
+            gloss(&words(&["học sinh", "đọc", "quyển", "sách", "đây"])?),
+            "The student reads this book."
+        );
+        assert_eq!(gloss(&words(&["Linh", "cao"])?), "Linh is tall.");
+        assert_eq!(
+            gloss(&words(&["Linh", "đã", "mua", "sách"])?),
+            "Linh bought the book."
+        );
+        assert_eq!(
+            gloss(&words(&["Linh", "đang", "viết", "thư"])?),
+            "Linh is writing the letter."
+        );
+        assert_eq!(
+            gloss(&words(&["Linh", "sẽ", "xem", "phim"])?),
+            "Linh will watch the film."
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("Phúc reads the book.", "Phúc read a book"));
+        assert!(matches("The dog has the book.", "the dog has books"));
+        // "Shoe" is not a meaning in the lexicon
+        assert!(!matches("The dog has shoes.", "the dog has a shoe"));
+        assert!(matches(
+            "The student reads this book.",
+            "student reads this book"
+        ));
+        assert!(!matches("Phúc reads the book.", "Phúc reads"));
+
+        // Only the endings of the gloss are ignored
+        assert!(matches("Linh watches the film.", "Linh watch the film"));
+        assert!(!matches("Linh watches the film.", "Linh watche the film"));
+        assert!(!matches("Linh hates the book.", "Linh hats the book"));
+        assert!(!matches("Linh is tall.", "Linh i tall"));
+        assert!(!matches("Linh takes the bus.", "Linh takes the bu"));
+        assert!(!matches("Linh uses the book.", "Linh us the book"));
+    }
+
+    #[test]
+    fn test_glosses() -> Result<()> {
+        let mut words = words(&["Linh", "xem", "phim"])?;
+        let xem = Word::from_metadata(
+            "verbs.txt",
+            "xem",
+            &vec![],
+            &["watch".to_string(), "see".to_string()],
+            vec![],
+        )?;
+        words[1] = xem;
+
+        assert_eq!(
+            glosses(&words),
+            vec!["Linh watches the film.", "Linh sees the film."]
+        );
+
+        Ok(())
+    }
+}
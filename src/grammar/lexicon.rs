@@ -0,0 +1,276 @@
+//! The words that sentences are generated from.
+//!
+//! The included word lists can be extended with the words of the user, like imported ones.
+use crate::grammar::{
+    defaults_parser::{self, Metadata},
+    dialect::Dialect,
+    etymology::Etymology,
+    structured, syllable,
+    word::{Class, CommonNounKind, Tense, Word},
+};
+use anyhow::{anyhow, bail, Result};
+use std::{fs, io::ErrorKind, path::Path, sync::OnceLock};
+
+/// The included word lists, the name decides the class of the words.
+pub const FILES: [(&str, &str); 8] = [
+    ("classifiers.txt", include_str!("../classifiers.txt")),
+    ("proper_nouns.txt", include_str!("../proper_nouns.txt")),
+    ("common_nouns.txt", include_str!("../common_nouns.txt")),
+    ("verbs.txt", include_str!("../verbs.txt")),
+    ("adjectives.txt", include_str!("../adjectives.txt")),
+    ("tense_markers.txt", include_str!("../tense_markers.txt")),
+    ("demonstratives.txt", include_str!("../demonstratives.txt")),
+    ("interjections.txt", include_str!("../interjections.txt")),
+];
+
+/// The words, set once when they're first used.
+static WORDS: OnceLock<Vec<Word>> = OnceLock::new();
+
+/// All words, the included ones followed by the ones passed to [`extend`].
+pub fn words() -> &'static [Word] {
+    WORDS.get_or_init(included)
+}
+
+/// Add words to the included ones.
+///
+/// Fails when the words are already used, so it must be called before generating anything.
+pub fn extend(words: Vec<Word>) -> Result<()> {
+    let mut all = included();
+    all.extend(words);
+
+    WORDS
+        .set(all)
+        .map_err(|_| anyhow!("The words can't be extended after they're used"))
+}
+
+/// Parse the included word lists.
+pub fn included() -> Vec<Word> {
+    FILES
+        .iter()
+        .flat_map(|(name, data)| Word::parse_file(name, data).expect("Included word list"))
+        .collect()
+}
+
+/// Read the word lists in a directory, named like the included ones, and the TOML and JSON
+/// files in the structured format.
+///
+/// Other files are ignored and a directory that doesn't exist has no words. A file that can't be
+/// loaded is skipped, the errors are returned with the words of the other files.
+pub fn read_dir(dir: &Path) -> (Vec<Word>, Vec<anyhow::Error>) {
+    let mut words = vec![];
+    let mut errors = vec![];
+    for (name, _) in FILES.iter() {
+        let path = dir.join(name);
+        match fs::read_to_string(&path) {
+            // The included word lists are linted, the ones of the user are checked the same way
+            Ok(data) => {
+                match check_list(name, &data).and_then(|()| Word::parse_file(name, &data)) {
+                    Ok(parsed) => words.extend(parsed),
+                    Err(err) => errors.push(err.context(format!("Could not load {:?}", path))),
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => (),
+            Err(err) => errors.push(anyhow!(err).context(format!("Could not read {:?}", path))),
+        }
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return (words, errors),
+        Err(err) => {
+            errors.push(anyhow!(err).context(format!("Could not read {:?}", dir)));
+            return (words, errors);
+        }
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| structured::is_structured(path))
+        .collect::<Vec<_>>();
+    paths.sort();
+    for path in paths {
+        match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| structured::parse(&path, &data))
+            .and_then(|lexicon| lexicon.words())
+        {
+            Ok(parsed) => words.extend(parsed),
+            Err(err) => errors.push(err.context(format!("Could not load {:?}", path))),
+        }
+    }
+
+    (words, errors)
+}
+
+/// The name of the word list with the words of a class, `None` for classes without one.
+pub fn file_name(class: &Class) -> Option<&'static str> {
+    match class {
+        Class::ClassifierNoun(_) => Some("classifiers.txt"),
+        Class::ProperNoun(_) => Some("proper_nouns.txt"),
+        Class::CommonNoun(_) => Some("common_nouns.txt"),
+        Class::Verb(_) => Some("verbs.txt"),
+        Class::Adjective(_) => Some("adjectives.txt"),
+        Class::TenseMarker(_) => Some("tense_markers.txt"),
+        Class::Demonstrative(_) => Some("demonstratives.txt"),
+        Class::Interjection => Some("interjections.txt"),
+        Class::Adverb | Class::Pronoun | Class::Conjunction | Class::Determiner => None,
+    }
+}
+
+/// The name of the class of the words in a word list, the reverse of [`file_name`].
+pub fn class_name(list: &str) -> Option<&'static str> {
+    [
+        "classifier",
+        "proper noun",
+        "common noun",
+        "verb",
+        "adjective",
+        "tense marker",
+        "demonstrative",
+        "interjection",
+    ]
+    .iter()
+    .copied()
+    .find(|class| {
+        class
+            .parse::<Class>()
+            .ok()
+            .and_then(|class| file_name(&class))
+            == Some(list)
+    })
+}
+
+/// The metadata keys the words of a word list can have, `None` if it's not a word list.
+pub fn keys(list: &str) -> Option<fn(&str) -> bool> {
+    Some(match list {
+        "classifiers.txt" | "demonstratives.txt" | "interjections.txt" => shared_key,
+        "proper_nouns.txt" => {
+            |key| matches!(key, "SUBJECT" | "OBJECT" | "PERSON" | "CLASSIFIER") || shared_key(key)
+        }
+        "common_nouns.txt" => |key| {
+            matches!(key, "SUBJECT" | "OBJECT" | "CLASSIFIER")
+                || key.parse::<CommonNounKind>().is_ok()
+                || shared_key(key)
+        },
+        "verbs.txt" => |key| key == "PAST" || shared_key(key),
+        "adjectives.txt" => |key| key == "INTENSIVE" || shared_key(key),
+        "tense_markers.txt" => |key| key.parse::<Tense>().is_ok() || shared_key(key),
+        _ => return None,
+    })
+}
+
+/// What's wrong with a line of a word list: the meaning, the spelling and the metadata.
+///
+/// Whether the classifiers exist and words aren't defined twice is checked by the lint, it knows
+/// the other words.
+pub fn line_problems(line: &str, keys: fn(&str) -> bool) -> Vec<String> {
+    let (word, metadata, meaning) = defaults_parser::parse_word_line(line);
+    let mut problems = vec![];
+
+    if meaning.is_none_or(str::is_empty) {
+        problems.push(format!("{} has no meaning", word));
+    }
+    if let Err(err) = syllable::check(word) {
+        problems.push(format!("{} is not spelled right: {}", word, err));
+    }
+
+    for (key, data) in metadata {
+        if !keys(key) {
+            problems.push(format!("Unknown metadata {}", key));
+            continue;
+        }
+
+        match (key, data) {
+            ("CLASSIFIER", None) => {
+                problems.push("CLASSIFIER needs a value, \"CLASSIFIER = cái\"".to_string())
+            }
+            ("MEANING", None) => {
+                problems.push("MEANING needs a value, \"MEANING = cup\"".to_string())
+            }
+            (key, Some(variant)) if key.parse::<Dialect>().is_ok() => {
+                if let Err(err) = syllable::check(variant) {
+                    problems.push(format!("Variant {} is not spelled right: {}", variant, err));
+                }
+            }
+            ("CLASSIFIER" | "MEANING" | "PAST" | "SINO", _) | (_, None) => (),
+            (key, Some(_)) => problems.push(format!("{} can't have a value", key)),
+        }
+    }
+
+    problems
+}
+
+/// Fail at the first line of a word list of the user with a problem.
+fn check_list(name: &str, data: &str) -> Result<()> {
+    let keys = keys(name).ok_or_else(|| anyhow!("{} is not a word list", name))?;
+    for (line, text) in defaults_parser::parse_lines(data) {
+        if let Some(problem) = line_problems(text, keys).first() {
+            bail!("Line {}: {}", line, problem);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the metadata key can be used for every word, the other meanings, dialects and origins.
+fn shared_key(key: &str) -> bool {
+    let metadata: Metadata = vec![(key, None)];
+
+    key == "MEANING"
+        || key.parse::<Dialect>().is_ok()
+        || Etymology::from_metadata(&metadata).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use std::env;
+
+    #[test]
+    fn test_file_name() -> Result<()> {
+        // Every included word list is found back from the classes of its words
+        for (name, data) in FILES.iter() {
+            for word in Word::parse_file(name, data)? {
+                assert_eq!(file_name(word.class()), Some(*name));
+            }
+            assert!(class_name(name).is_some());
+            assert!(keys(name).is_some_and(|keys| keys("NATIVE")));
+        }
+        assert!(Word::parse_file("lessons.txt", "").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_dir() -> Result<()> {
+        let dir = env::temp_dir().join(format!("tieng-viet-lexicon-{}", std::process::id()));
+        assert!(read_dir(&dir).0.is_empty());
+
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("verbs.txt"), "# Imported\nbơi: swim\n")?;
+        fs::write(dir.join("notes.txt"), "not a word list")?;
+        fs::write(
+            dir.join("animals.toml"),
+            "[[words]]\nword = \"cá heo\"\nclass = \"common noun\"\nmeanings = [\"dolphin\"]\n",
+        )?;
+        fs::write(dir.join("broken.json"), "{\"words\": 1}")?;
+        fs::write(dir.join("adjectives.txt"), "xyz: wrong\n")?;
+        fs::write(dir.join("classifiers.txt"), "cái(SIZE): item\n")?;
+        let (words, errors) = read_dir(&dir);
+        fs::remove_dir_all(&dir)?;
+
+        // The broken files don't stop the others from being loaded
+        assert_eq!(errors.len(), 3);
+        assert!(format!("{:#}", errors[0]).contains("Line 1: Unknown metadata SIZE"));
+        assert!(format!("{:#}", errors[1]).contains("xyz is not spelled right"));
+        assert!(format!("{:#}", errors[2]).contains("broken.json"));
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].to_string(), "bơi");
+        assert_eq!(words[0].meaning(), "swim");
+        assert_eq!(words[0].class().to_string(), "verb");
+        assert_eq!(words[1].to_string(), "cá heo");
+        assert_eq!(words[1].class().to_string(), "common noun");
+
+        Ok(())
+    }
+}
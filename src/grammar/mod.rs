@@ -1,11 +1,27 @@
-mod defaults_parser;
+//! The words, how they're written and pronounced and how they form sentences.
+
+pub mod defaults_parser;
+pub mod dialect;
+pub mod etymology;
+pub mod gloss;
+pub mod lexicon;
+pub mod phonetics;
 pub mod phrase;
+pub mod record;
+pub mod reduplication;
+pub mod segmenter;
 pub mod sentence;
+pub mod structured;
+pub mod syllable;
 pub mod word;
 
-use crate::grammar::word::Word;
+use crate::grammar::{
+    dialect::Dialect,
+    word::{Class, Word},
+};
 use anyhow::{anyhow, Result};
 use rand::{seq::IteratorRandom, Rng};
+use std::fmt::Display;
 
 /// Add functionality that generates random phrases.
 pub trait Generate {
@@ -16,19 +32,26 @@ pub trait Generate {
     fn allowed_structures() -> Box<dyn Iterator<Item = Self::StructureItem>>;
 
     /// Get a list of default words generated from a chosen structure.
-    fn default_words<'a, R>(
-        rng: &'a mut R,
+    fn default_words<R>(
+        rng: &mut R,
         structure: Self::StructureItem,
         metadata: Vec<&str>,
     ) -> Result<Box<dyn Iterator<Item = Word>>>
     where
         R: Rng;
 
+    /// Find all ways the start of the words can be formed from the allowed structures.
+    fn derivations(words: &[Word], metadata: Vec<&str>) -> Vec<Derivation>;
+
+    /// Find how all words together are formed from one of the allowed structures.
+    fn parse(words: &[Word], metadata: Vec<&str>) -> Option<Derivation> {
+        Self::derivations(words, metadata)
+            .into_iter()
+            .find(|derivation| derivation.len == words.len())
+    }
+
     /// Generate a sentence from the allowed structures.
-    fn generate<'a, R>(
-        rng: &'a mut R,
-        metadata: Vec<&str>,
-    ) -> Result<Box<dyn Iterator<Item = Word>>>
+    fn generate<R>(rng: &mut R, metadata: Vec<&str>) -> Result<Box<dyn Iterator<Item = Word>>>
     where
         R: Rng,
     {
@@ -40,4 +63,108 @@ pub trait Generate {
         // Get the default words from the structure.
         Self::default_words(rng, structure, metadata)
     }
+
+    /// Generate a sentence with the words as they are used in a dialect.
+    fn generate_in_dialect<R>(
+        rng: &mut R,
+        metadata: Vec<&str>,
+        dialect: Dialect,
+    ) -> Result<Box<dyn Iterator<Item = Word>>>
+    where
+        R: Rng,
+    {
+        Ok(Box::new(
+            Self::generate(rng, metadata)?.map(move |word| word.in_dialect(dialect)),
+        ))
+    }
+}
+
+/// How a sequence of words is formed from one of the allowed structures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Derivation {
+    /// The file the structure is defined in.
+    pub source: &'static str,
+    /// The structure as it's defined in the file.
+    pub structure: Vec<String>,
+    /// How the sub-structures in the structure are formed.
+    pub children: Vec<Derivation>,
+    /// Where the formed words start, counted from the start of the parent structure.
+    pub start: usize,
+    /// The amount of words that are formed.
+    pub len: usize,
+}
+
+impl Derivation {
+    /// Find all ways the structure forms the start of the words.
+    ///
+    /// The function is called for every item in the structure with the remaining words, and
+    /// returns all amounts of words the item can form with the derivation of the sub-structure.
+    pub fn match_structure<F>(
+        source: &'static str,
+        structure: Vec<String>,
+        words: &[Word],
+        item: F,
+    ) -> Vec<Derivation>
+    where
+        F: Fn(&str, &[Word]) -> Vec<(usize, Option<Derivation>)>,
+    {
+        Self::match_items(&structure, words, &item)
+            .into_iter()
+            .map(|(len, children)| Derivation {
+                source,
+                structure: structure.clone(),
+                children,
+                start: 0,
+                len,
+            })
+            .collect()
+    }
+
+    /// Match a single word of a class.
+    pub fn match_word(words: &[Word], class: Class) -> Vec<(usize, Option<Derivation>)> {
+        match words.first() {
+            Some(word) if word.class().fits(&class) => vec![(1, None)],
+            _ => vec![],
+        }
+    }
+
+    /// All structures used, starting with the outermost one.
+    pub fn structures(&self) -> Vec<&Derivation> {
+        std::iter::once(self)
+            .chain(self.children.iter().flat_map(|child| child.structures()))
+            .collect()
+    }
+
+    fn match_items<F>(items: &[String], words: &[Word], item: &F) -> Vec<(usize, Vec<Derivation>)>
+    where
+        F: Fn(&str, &[Word]) -> Vec<(usize, Option<Derivation>)>,
+    {
+        match items.split_first() {
+            None => vec![(0, vec![])],
+            Some((first, rest)) => item(first, words)
+                .into_iter()
+                .flat_map(|(len, child)| {
+                    Self::match_items(rest, &words[len..], item)
+                        .into_iter()
+                        .map(move |(rest_len, mut children)| {
+                            // The rest starts after the words of the first item
+                            for child in &mut children {
+                                child.start += len;
+                            }
+                            if let Some(child) = child.clone() {
+                                children.insert(0, child);
+                            }
+
+                            (len + rest_len, children)
+                        })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Display for Derivation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.structure.join(" + "), self.source)
+    }
 }
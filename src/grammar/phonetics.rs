@@ -0,0 +1,209 @@
+//! The pronunciation of words in IPA.
+
+use crate::grammar::{
+    dialect::Dialect,
+    syllable::{self, Syllable, Tone},
+};
+use anyhow::Result;
+
+/// Convert a single syllable to IPA for a dialect.
+///
+/// **Đọc**: ɗɔk͡p˨˩ (Northern).
+///
+/// See: <https://en.wikipedia.org/wiki/Help:IPA/Vietnamese>
+pub fn syllable_to_ipa(syllable: &Syllable, dialect: Dialect) -> String {
+    let mut ipa = String::new();
+
+    ipa.push_str(initial(&syllable.initial, dialect));
+    if syllable.medial.is_some() {
+        ipa.push('w');
+    }
+    ipa.push_str(&rhyme(syllable, dialect));
+    ipa.push_str(tone(syllable.tone, syllable.is_checked(), dialect));
+
+    ipa
+}
+
+/// Convert a text of syllables separated by whitespace or hyphens to IPA.
+///
+/// **Tiếng Việt**: tiəŋ˧˥ viət˨˩ (Northern).
+pub fn transcribe(text: &str, dialect: Dialect) -> Result<String> {
+    Ok(syllable::split(text)
+        .map(|syllable| {
+            syllable
+                .parse::<Syllable>()
+                .map(|syllable| syllable_to_ipa(&syllable, dialect))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(" "))
+}
+
+/// The onset, d, gi and r merge in the north while v and d merge in the south.
+fn initial(initial: &str, dialect: Dialect) -> &'static str {
+    match (initial, dialect) {
+        ("", _) => "ʔ",
+        ("b", _) => "ɓ",
+        ("c", _) | ("k", _) => "k",
+        ("ch", _) => "c",
+        ("đ", _) => "ɗ",
+        ("g", _) | ("gh", _) => "ɣ",
+        ("h", _) => "h",
+        ("kh", _) => "x",
+        ("l", _) => "l",
+        ("m", _) => "m",
+        ("n", _) => "n",
+        ("ng", _) | ("ngh", _) => "ŋ",
+        ("nh", _) => "ɲ",
+        ("p", _) => "p",
+        ("ph", _) => "f",
+        ("t", _) => "t",
+        ("th", _) => "tʰ",
+        ("x", _) => "s",
+        ("d", Dialect::Northern) | ("gi", Dialect::Northern) | ("r", Dialect::Northern) => "z",
+        ("d", Dialect::Southern) | ("gi", Dialect::Southern) | ("v", Dialect::Southern) => "j",
+        ("r", Dialect::Southern) => "ɹ",
+        ("v", Dialect::Northern) => "v",
+        ("s", Dialect::Northern) => "s",
+        ("s", Dialect::Southern) => "ʂ",
+        ("tr", Dialect::Northern) => "c",
+        ("tr", Dialect::Southern) => "ʈ",
+        ("qu", Dialect::Northern) => "kw",
+        ("qu", Dialect::Southern) => "w",
+        _ => "",
+    }
+}
+
+/// The vowel and final consonant.
+fn rhyme(syllable: &Syllable, dialect: Dialect) -> String {
+    let coda = syllable.coda.as_str();
+    let front = matches!(syllable.nucleus.as_str(), "i" | "y" | "ê" | "e");
+    let rounded = matches!(syllable.nucleus.as_str(), "o" | "ô" | "u");
+
+    // "ay" and "au" have a short vowel compared to "ai" and "ao"
+    let nucleus = match (syllable.nucleus.as_str(), coda) {
+        ("a", "y") | ("a", "u") => "a",
+        ("a", "nh") | ("a", "ch") => match dialect {
+            Dialect::Northern => "aj",
+            Dialect::Southern => "a",
+        },
+        ("ê", "nh") | ("ê", "ch") if dialect == Dialect::Northern => "ej",
+        ("ê", "nh") | ("ê", "ch") | ("i", "nh") | ("i", "ch") | ("y", "nh") | ("y", "ch") => {
+            match dialect {
+                Dialect::Northern => "i",
+                Dialect::Southern => "ɨ",
+            }
+        }
+        ("a", _) => "aː",
+        ("ă", _) => "a",
+        ("â", _) => "ə",
+        ("e", _) => "ɛ",
+        ("ê", _) => "e",
+        ("i", _) | ("y", _) => "i",
+        ("o", _) => "ɔ",
+        ("ô", _) => "o",
+        ("ơ", _) => "əː",
+        ("u", _) => "u",
+        ("ư", _) => "ɨ",
+        ("iê", _) | ("yê", _) | ("ia", _) | ("ya", _) => "iə",
+        ("uô", _) | ("ua", _) => "uə",
+        ("ươ", _) | ("ưa", _) => "ɨə",
+        ("oo", _) => "ɔː",
+        ("ôô", _) => "oː",
+        _ => "",
+    };
+
+    let coda = match (coda, dialect) {
+        ("i", _) | ("y", _) => "j",
+        ("o", _) | ("u", _) => "w",
+        ("m", _) => "m",
+        ("p", _) => "p",
+        // Rounded vowels close with the lips as well
+        ("ng", _) if rounded => "ŋ͡m",
+        ("c", _) if rounded => "k͡p",
+        ("ng", Dialect::Northern) | ("nh", Dialect::Northern) => "ŋ",
+        ("c", Dialect::Northern) | ("ch", Dialect::Northern) => "k",
+        ("n", Dialect::Northern) => "n",
+        ("t", Dialect::Northern) => "t",
+        // In the south final n & ng and t & c merge, only staying alveolar after front vowels
+        ("nh", Dialect::Southern) => "n",
+        ("ch", Dialect::Southern) => "t",
+        ("n", Dialect::Southern) | ("ng", Dialect::Southern) if front => "n",
+        ("t", Dialect::Southern) | ("c", Dialect::Southern) if front => "t",
+        ("n", Dialect::Southern) | ("ng", Dialect::Southern) => "ŋ",
+        ("t", Dialect::Southern) | ("c", Dialect::Southern) => "k",
+        _ => "",
+    };
+
+    format!("{}{}", nucleus, coda)
+}
+
+/// The tone as Chao tone letters, hỏi and ngã merge in the south.
+fn tone(tone: Tone, checked: bool, dialect: Dialect) -> &'static str {
+    match (tone, checked, dialect) {
+        (Tone::Ngang, _, _) => "˧",
+        (Tone::Huyen, _, _) => "˨˩",
+        (Tone::Sac, true, _) => "˦˥",
+        (Tone::Sac, false, _) => "˧˥",
+        (Tone::Nang, true, _) => "˨˩",
+        (Tone::Hoi, _, Dialect::Northern) => "˧˩˧",
+        (Tone::Nga, _, Dialect::Northern) => "˧ˀ˥",
+        (Tone::Nang, false, Dialect::Northern) => "˨˩ˀ",
+        (Tone::Hoi, _, Dialect::Southern) | (Tone::Nga, _, Dialect::Southern) => "˨˩˦",
+        (Tone::Nang, false, Dialect::Southern) => "˨˩˨",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_initials() -> Result<()> {
+        assert_eq!(transcribe("da", Dialect::Northern)?, "zaː˧");
+        assert_eq!(transcribe("gia", Dialect::Northern)?, "zaː˧");
+        assert_eq!(transcribe("ra", Dialect::Northern)?, "zaː˧");
+        assert_eq!(transcribe("da", Dialect::Southern)?, "jaː˧");
+        assert_eq!(transcribe("va", Dialect::Southern)?, "jaː˧");
+        assert_eq!(transcribe("ra", Dialect::Southern)?, "ɹaː˧");
+        assert_eq!(transcribe("ăn", Dialect::Northern)?, "ʔan˧");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finals() -> Result<()> {
+        assert_eq!(transcribe("anh", Dialect::Northern)?, "ʔajŋ˧");
+        assert_eq!(transcribe("anh", Dialect::Southern)?, "ʔan˧");
+        assert_eq!(transcribe("lan", Dialect::Southern)?, "laːŋ˧");
+        assert_eq!(transcribe("lang", Dialect::Southern)?, "laːŋ˧");
+        assert_eq!(transcribe("mát", Dialect::Southern)?, "maːk˦˥");
+        assert_eq!(transcribe("tin", Dialect::Southern)?, "tin˧");
+        assert_eq!(transcribe("đọc", Dialect::Northern)?, "ɗɔk͡p˨˩");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tones() -> Result<()> {
+        assert_eq!(transcribe("mả", Dialect::Northern)?, "maː˧˩˧");
+        assert_eq!(transcribe("mã", Dialect::Northern)?, "maː˧ˀ˥");
+        assert_eq!(
+            transcribe("mả", Dialect::Southern)?,
+            transcribe("mã", Dialect::Southern)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transcribe() -> Result<()> {
+        assert_eq!(
+            transcribe("Tiếng Việt.", Dialect::Northern)?,
+            "tiəŋ˧˥ viət˨˩"
+        );
+        assert!(transcribe("xyz", Dialect::Northern).is_err());
+
+        Ok(())
+    }
+}
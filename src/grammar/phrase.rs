@@ -1,12 +1,14 @@
-use crate::grammar::{defaults_parser, word::*, Generate};
+//! Noun and verb phrases, the parts sentences are built from.
+
+use crate::grammar::{defaults_parser, word::*, Derivation, Generate};
 use anyhow::{anyhow, Result};
 use log::debug;
-use rand::Rng;
+use rand::{seq::IteratorRandom, Rng};
 use std::iter;
 
 /// A phrase with a noun as it's head.
 ///
-/// See: https://en.wikipedia.org/wiki/Noun_phrase
+/// See: <https://en.wikipedia.org/wiki/Noun_phrase>
 pub struct NounPhrase {}
 
 impl Generate for NounPhrase {
@@ -23,23 +25,47 @@ impl Generate for NounPhrase {
         )
     }
 
-    fn default_words<'a, R>(
-        rng: &'a mut R,
+    fn derivations(words: &[Word], metadata: Vec<&str>) -> Vec<Derivation> {
+        Self::allowed_structures()
+            .flat_map(|structure| {
+                Derivation::match_structure("noun_phrases.txt", structure, words, |item, words| {
+                    match item {
+                        "DEMONSTRATIVE" => Derivation::match_word(
+                            words,
+                            Class::Demonstrative(Demonstrative::default()),
+                        ),
+                        "CLASSIFIER" => Derivation::match_word(
+                            words,
+                            Class::ClassifierNoun(ClassifierNoun::default()),
+                        ),
+                        "HEAD" => head_classes(&metadata)
+                            .into_iter()
+                            .flat_map(|class| Derivation::match_word(words, class))
+                            .collect(),
+                        _ => vec![],
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn default_words<R>(
+        rng: &mut R,
         structure: Self::StructureItem,
         metadata: Vec<&str>,
     ) -> Result<Box<dyn Iterator<Item = Word>>>
     where
         R: Rng,
     {
-        // If the structure contains a classifier use that as the base
-        let classifier = if structure.iter().any(|item| item == "CLASSIFIER") {
-            Some(Word::random_default(
-                rng,
-                Class::ClassifierNoun(ClassifierNoun::default()),
-            )?)
-        } else {
-            None
-        };
+        // If the structure contains a classifier the head must have one
+        let has_classifier = structure.iter().any(|item| item == "CLASSIFIER");
+        let head_classes = head_classes(&metadata);
+        let head = Word::defaults()
+            .filter(|word| head_classes.iter().any(|class| word.class().fits(class)))
+            .filter(|word| !has_classifier || word.has_classifier())
+            .choose(rng)
+            .ok_or_else(|| anyhow!("Could not get random head for {:?}", structure))?;
+        let classifier = head.classifier();
 
         debug!("NP: {:?}", metadata);
         Ok(Box::new(
@@ -50,16 +76,10 @@ impl Generate for NounPhrase {
                     "DEMONSTRATIVE" => {
                         Word::random_default(rng, Class::Demonstrative(Demonstrative::default()))
                     }
-                    // Can be safely unwrapped because it's defined above
-                    "CLASSIFIER" => Ok(classifier.as_ref().unwrap().clone()),
-                    "HEAD" => Word::random_default(
-                        rng,
-                        Class::ProperNoun(ProperNoun {
-                            is_object: metadata.iter().any(|m| *m == "OBJECT"),
-                            is_subject: metadata.iter().any(|m| *m == "SUBJECT"),
-                            classifier: classifier.as_ref().map(|word| word.to_string()),
-                        }),
-                    ),
+                    "CLASSIFIER" => classifier
+                        .clone()
+                        .ok_or_else(|| anyhow!("{} has no classifier", head)),
+                    "HEAD" => Ok(head.clone()),
                     _ => Err(anyhow!("Unrecognized structure item {}", item)),
                 })
                 // Collect the vector so the random number generator is consumed.
@@ -70,6 +90,27 @@ impl Generate for NounPhrase {
     }
 }
 
+/// The classes of nouns that can be the head of a noun phrase as a subject or object.
+fn head_classes(metadata: &[&str]) -> Vec<Class> {
+    let is_object = metadata.contains(&"OBJECT");
+    let is_subject = metadata.contains(&"SUBJECT");
+
+    vec![
+        Class::ProperNoun(ProperNoun {
+            is_object,
+            is_subject,
+            is_person: false,
+            classifier: None,
+        }),
+        Class::CommonNoun(CommonNoun {
+            is_object,
+            is_subject,
+            kind: CommonNounKind::Item,
+            classifier: None,
+        }),
+    ]
+}
+
 /// A phrase with a verb as it's head.
 pub struct VerbPhrase {}
 
@@ -87,8 +128,33 @@ impl Generate for VerbPhrase {
         )
     }
 
-    fn default_words<'a, R>(
-        rng: &'a mut R,
+    fn derivations(words: &[Word], metadata: Vec<&str>) -> Vec<Derivation> {
+        Self::allowed_structures()
+            .flat_map(|structure| {
+                Derivation::match_structure("verb_phrases.txt", structure, words, |item, words| {
+                    match item {
+                        "TENSE" => Derivation::match_word(words, Class::TenseMarker(Tense::Past)),
+                        "VERB" => Derivation::match_word(words, Class::Verb(Verb::default())),
+                        "NOUN" => NounPhrase::derivations(
+                            words,
+                            metadata
+                                .clone()
+                                .into_iter()
+                                .chain(iter::once("OBJECT"))
+                                .collect(),
+                        )
+                        .into_iter()
+                        .map(|derivation| (derivation.len, Some(derivation)))
+                        .collect(),
+                        _ => vec![],
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn default_words<R>(
+        rng: &mut R,
         structure: Self::StructureItem,
         metadata: Vec<&str>,
     ) -> Result<Box<dyn Iterator<Item = Word>>>
@@ -101,6 +167,10 @@ impl Generate for VerbPhrase {
                 .into_iter()
                 // Loop over all items in the structure and map them to the sub-structures
                 .map(|item| match item.as_str() {
+                    "TENSE" => Ok(vec![Word::random_default(
+                        rng,
+                        Class::TenseMarker(Tense::Past),
+                    )?]),
                     "VERB" => Ok(vec![Word::random_default(
                         rng,
                         Class::Verb(Verb::default()),
@@ -134,10 +204,28 @@ mod tests {
     #[test]
     fn test_allowed() -> Result<()> {
         let allowed = NounPhrase::allowed_structures().collect::<Vec<_>>();
-        assert!(allowed.len() > 0);
+        assert!(!allowed.is_empty());
 
         let allowed = VerbPhrase::allowed_structures().collect::<Vec<_>>();
-        assert!(allowed.len() > 0);
+        assert!(!allowed.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_derivations() -> Result<()> {
+        let mut rng = rand::thread_rng();
+
+        // Every generated phrase must be formed by one of the structures
+        for _ in 0..20 {
+            let words = NounPhrase::generate(&mut rng, vec!["OBJECT"])?.collect::<Vec<_>>();
+            assert!(NounPhrase::parse(&words, vec!["OBJECT"]).is_some());
+
+            let words = VerbPhrase::generate(&mut rng, vec![])?.collect::<Vec<_>>();
+            let derivation = VerbPhrase::parse(&words, vec![]).unwrap();
+            assert_eq!(derivation.source, "verb_phrases.txt");
+            assert_eq!(derivation.children.len(), 1);
+        }
 
         Ok(())
     }
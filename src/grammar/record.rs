@@ -0,0 +1,257 @@
+//! Generated sentences with how they're formed, as JSON lines or CSV.
+
+use crate::{
+    grammar::{
+        gloss,
+        phrase::{NounPhrase, VerbPhrase},
+        sentence::Sentence,
+        word::Word,
+        Generate,
+    },
+    settings::Settings,
+};
+use anyhow::{anyhow, bail, Result};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// How many sentences are generated to find one with the structure and origin.
+const ATTEMPTS: usize = 1000;
+/// Separates the words, classes and structures in a CSV column.
+const CSV_SEPARATOR: &str = "; ";
+
+/// A generated sentence with how it's formed, to use in other tools.
+///
+/// **Tôi đọc sách**: I read the book, SUBJECT + VP.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// The words separated by spaces.
+    pub sentence: String,
+    /// The words with their classes and meanings.
+    pub words: Vec<Word>,
+    /// How every word is pronounced in the dialect, in IPA.
+    #[serde(default)]
+    pub pronunciations: Vec<String>,
+    /// Literal English translation.
+    pub translation: String,
+    /// All structures the sentence is formed with, starting with the outermost one.
+    pub structures: Vec<String>,
+    /// Generating a sentence with this seed gives the same sentence again.
+    pub seed: u64,
+}
+
+impl Record {
+    /// Generate a sentence from the seed, formed with the structure when it's passed.
+    ///
+    /// The sentence is in the dialect of the settings and has a word of the chosen origin.
+    pub fn generate(seed: u64, structure: Option<&str>, settings: &Settings) -> Result<Self> {
+        let structure = structure.map(normalize).transpose()?;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..ATTEMPTS {
+            let words = Sentence::generate_in_dialect(&mut rng, vec![], settings.dialect)?
+                .collect::<Vec<_>>();
+            if !settings.allows_sentence(&words) {
+                continue;
+            }
+
+            let derivation = Sentence::parse(&words, vec![])
+                .ok_or_else(|| anyhow!("Generated sentence can't be parsed"))?;
+            let structures = derivation
+                .structures()
+                .into_iter()
+                .map(|derivation| derivation.to_string())
+                .collect::<Vec<_>>();
+
+            let formed = structure.as_ref().is_none_or(|structure| {
+                structures
+                    .iter()
+                    .any(|used| used.starts_with(&format!("{} (", structure)))
+            });
+            if formed {
+                return Ok(Record {
+                    sentence: words
+                        .iter()
+                        .map(|word| word.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    translation: gloss::gloss(&words),
+                    pronunciations: words
+                        .iter()
+                        .map(|word| word.pronunciation(settings.dialect))
+                        .collect::<Result<_>>()?,
+                    words,
+                    structures,
+                    seed,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "Could not generate a sentence with the structure and words of the chosen origin"
+        ))
+    }
+}
+
+/// Generate sentences, the seeds count up from the seed.
+pub fn generate(
+    seed: u64,
+    count: usize,
+    structure: Option<&str>,
+    settings: &Settings,
+) -> Result<Vec<Record>> {
+    (0..count as u64)
+        .map(|index| Record::generate(seed.wrapping_add(index), structure, settings))
+        .collect()
+}
+
+/// Write every record as a JSON object on a line.
+pub fn write_json_lines<W>(mut writer: W, records: &[Record]) -> Result<()>
+where
+    W: Write,
+{
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Write the records as CSV with a header, the lists are separated by semicolons.
+pub fn write_csv<W>(writer: W, records: &[Record]) -> Result<()>
+where
+    W: Write,
+{
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record([
+        "seed",
+        "sentence",
+        "translation",
+        "words",
+        "classes",
+        "meanings",
+        "pronunciations",
+        "structures",
+    ])?;
+
+    for record in records {
+        let join = |field: fn(&Word) -> String| {
+            record
+                .words
+                .iter()
+                .map(field)
+                .collect::<Vec<_>>()
+                .join(CSV_SEPARATOR)
+        };
+
+        csv.write_record([
+            record.seed.to_string(),
+            record.sentence.clone(),
+            record.translation.clone(),
+            join(|word| word.to_string()),
+            join(|word| word.class().to_string()),
+            join(|word| word.meaning().to_string()),
+            record.pronunciations.join(CSV_SEPARATOR),
+            record.structures.join(CSV_SEPARATOR),
+        ])?;
+    }
+    csv.flush()?;
+
+    Ok(())
+}
+
+/// Write the structure the same way as in the files, "CLASSIFIER + HEAD".
+///
+/// Fails when it's not one of the allowed structures.
+fn normalize(structure: &str) -> Result<String> {
+    let normalized = structure
+        .split('+')
+        .map(|item| item.trim().to_uppercase())
+        .collect::<Vec<_>>();
+
+    let allowed = Sentence::allowed_structures()
+        .chain(NounPhrase::allowed_structures())
+        .chain(VerbPhrase::allowed_structures())
+        .collect::<Vec<_>>();
+    if !allowed.contains(&normalized) {
+        bail!(
+            "Unknown structure \"{}\", the structures are: {}",
+            structure,
+            allowed
+                .iter()
+                .map(|structure| structure.join(" + "))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(normalized.join(" + "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::etymology::Etymology;
+    use anyhow::Result;
+
+    #[test]
+    fn test_generate() -> Result<()> {
+        let settings = Settings::default();
+
+        // The same seed generates the same sentences
+        let records = generate(1, 5, None, &settings)?;
+        assert_eq!(records.len(), 5);
+        assert_eq!(records, generate(1, 5, None, &settings)?);
+        assert_eq!(records[2], Record::generate(3, None, &settings)?);
+
+        let records = generate(1, 5, Some("classifier + head"), &settings)?;
+        assert!(records.iter().all(|record| record
+            .structures
+            .contains(&"CLASSIFIER + HEAD (noun_phrases.txt)".to_string())));
+
+        assert!(Record::generate(1, Some("HEAD + VERB"), &settings).is_err());
+
+        // Every sentence has a word of the origin
+        let origin = Etymology::SinoVietnamese(None);
+        let settings = Settings {
+            origin: Some(origin.clone()),
+            ..Settings::default()
+        };
+        let records = generate(1, 5, None, &settings)?;
+        assert!(records
+            .iter()
+            .all(|record| record.words.iter().any(|word| word.has_origin(&origin))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write() -> Result<()> {
+        let records = generate(1, 3, None, &Settings::default())?;
+
+        let mut json = vec![];
+        write_json_lines(&mut json, &records)?;
+        let parsed = String::from_utf8(json)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Record>, _>>()?;
+        assert_eq!(parsed, records);
+
+        let mut csv = vec![];
+        write_csv(&mut csv, &records)?;
+        let csv = String::from_utf8(csv)?;
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("seed,sentence,translation,words,classes,meanings,pronunciations,structures")
+        );
+        assert!(lines
+            .next()
+            .is_some_and(|line| line.starts_with(&format!("1,{},", records[0].sentence))));
+        assert_eq!(lines.count(), 2);
+        assert_eq!(records[0].pronunciations.len(), records[0].words.len());
+
+        Ok(())
+    }
+}
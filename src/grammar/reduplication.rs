@@ -0,0 +1,157 @@
+//! New words formed by repeating syllables.
+
+use crate::grammar::{
+    syllable::{Syllable, Tone},
+    word::{Class, Word},
+};
+use anyhow::{bail, Result};
+use std::fmt::Display;
+
+/// A way of forming a new word by repeating (part of) a syllable.
+///
+/// See: <https://en.wikipedia.org/wiki/Reduplication#Vietnamese>
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Reduplication {
+    /// Repeat the syllable before it, making the meaning less intense.
+    ///
+    /// **Đo đỏ**: Reddish.
+    Softened,
+    /// Repeat the syllable twice after it, making the meaning more intense.
+    ///
+    /// **Sạch sành sanh**: Spotlessly clean.
+    Intensified,
+}
+
+impl Reduplication {
+    /// The reduplicated form of a word.
+    ///
+    /// Only adjectives of a single syllable can be reduplicated, and only adjectives marked as
+    /// such can be intensified.
+    pub fn apply(self, word: &Word) -> Result<String> {
+        let intensifiable = match word.class() {
+            Class::Adjective(adjective) => adjective.intensifiable,
+            _ => bail!("Only adjectives can be reduplicated, {} is not", word),
+        };
+
+        let syllables = word.syllables()?;
+        let base = match syllables.as_slice() {
+            [base] => base,
+            _ => bail!("Only single syllables can be reduplicated, {} is not", word),
+        };
+
+        Ok(match self {
+            Reduplication::Softened => format!("{} {}", softened(base), base),
+            Reduplication::Intensified if intensifiable && base.is_checked() => format!(
+                "{} {} {}",
+                base,
+                with_nasal_coda(base, Tone::Huyen),
+                with_nasal_coda(base, Tone::Ngang)
+            ),
+            Reduplication::Intensified => bail!("{} can not be intensified", word),
+        })
+    }
+
+    /// Whether the word can be reduplicated this way.
+    pub fn applies_to(self, word: &Word) -> bool {
+        self.apply(word).is_ok()
+    }
+
+    /// The meaning of the reduplicated form.
+    ///
+    /// **Đo đỏ**: Somewhat red.
+    pub fn meaning(self, meaning: &str) -> String {
+        match self {
+            Reduplication::Softened => format!("somewhat {}", meaning),
+            Reduplication::Intensified => format!("extremely {}", meaning),
+        }
+    }
+}
+
+impl Display for Reduplication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reduplication::Softened => write!(f, "softened"),
+            Reduplication::Intensified => write!(f, "intensified"),
+        }
+    }
+}
+
+/// The syllable that is put before the base to soften it.
+///
+/// The tone follows the register of the base: sắc and hỏi become ngang, nặng and ngã become
+/// huyền. A final stop becomes the nasal made at the same place in the mouth.
+///
+/// - **Đỏ**: đo đỏ.
+/// - **Nhẹ**: nhè nhẹ.
+/// - **Mát**: man mát.
+fn softened(base: &Syllable) -> Syllable {
+    let tone = match base.tone {
+        Tone::Ngang | Tone::Sac | Tone::Hoi => Tone::Ngang,
+        Tone::Huyen | Tone::Nga | Tone::Nang => Tone::Huyen,
+    };
+
+    with_nasal_coda(base, tone)
+}
+
+/// Change a final stop to a nasal and set the tone.
+fn with_nasal_coda(base: &Syllable, tone: Tone) -> Syllable {
+    let coda = match base.coda.as_str() {
+        "p" => "m",
+        "t" => "n",
+        "c" => "ng",
+        "ch" => "nh",
+        coda => coda,
+    };
+
+    Syllable {
+        coda: coda.to_string(),
+        tone,
+        ..base.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Result};
+
+    fn adjective(content: &str) -> Result<Word> {
+        Word::defaults()
+            .find(|word| word.to_string() == content)
+            .ok_or_else(|| anyhow!("Word {} not found", content))
+    }
+
+    #[test]
+    fn test_softened() -> Result<()> {
+        for (base, reduplicated) in &[
+            ("xanh", "xanh xanh"),
+            ("vàng", "vàng vàng"),
+            ("đỏ", "đo đỏ"),
+            ("trắng", "trăng trắng"),
+            ("nhẹ", "nhè nhẹ"),
+            ("lạnh", "lành lạnh"),
+            ("mát", "man mát"),
+            ("khác", "khang khác"),
+            ("đẹp", "đèm đẹp"),
+        ] {
+            assert_eq!(
+                Reduplication::Softened.apply(&adjective(base)?)?,
+                *reduplicated
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intensified() -> Result<()> {
+        assert_eq!(
+            Reduplication::Intensified.apply(&adjective("sạch")?)?,
+            "sạch sành sanh"
+        );
+        assert!(!Reduplication::Intensified.applies_to(&adjective("đỏ")?));
+        assert!(!Reduplication::Softened.applies_to(&adjective("sách")?));
+
+        Ok(())
+    }
+}
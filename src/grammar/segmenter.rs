@@ -0,0 +1,105 @@
+//! Splitting text into the words of the lexicon.
+
+use crate::grammar::{syllable, word::Word};
+
+/// A part of a segmented text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// A word found in the lexicon, with the text as it was written.
+    Known(Word, String),
+    /// A syllable that doesn't start any word in the lexicon.
+    Unknown(String),
+}
+
+impl Segment {
+    /// The text as it was written.
+    pub fn text(&self) -> &str {
+        match self {
+            Segment::Known(_, text) | Segment::Unknown(text) => text,
+        }
+    }
+}
+
+/// Split a text into words by always taking the longest word in the lexicon.
+///
+/// All written forms of the words, including dialect variants, are matched.
+///
+/// **Học sinh đọc sách**: học sinh, đọc, sách.
+pub fn segment(text: &str, lexicon: &[Word]) -> Vec<Segment> {
+    let syllables = syllable::split(text).collect::<Vec<_>>();
+
+    // All forms in the lexicon as lowercase syllables
+    let forms = lexicon
+        .iter()
+        .flat_map(|word| {
+            word.accepted_forms(None).into_iter().map(move |form| {
+                (
+                    word,
+                    syllable::split(form)
+                        .map(str::to_lowercase)
+                        .collect::<Vec<_>>(),
+                )
+            })
+        })
+        .filter(|(_, form)| !form.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut segments = vec![];
+    let mut index = 0;
+    while index < syllables.len() {
+        let rest = &syllables[index..];
+
+        // Find the form with the most syllables that matches the start of the rest
+        let longest = forms
+            .iter()
+            .filter(|(_, form)| {
+                form.len() <= rest.len()
+                    && form
+                        .iter()
+                        .zip(rest)
+                        .all(|(expected, syllable)| *expected == syllable.to_lowercase())
+            })
+            .max_by_key(|(_, form)| form.len());
+
+        match longest {
+            Some((word, form)) => {
+                segments.push(Segment::Known(
+                    (*word).clone(),
+                    rest[..form.len()].join(" "),
+                ));
+                index += form.len();
+            }
+            None => {
+                segments.push(Segment::Unknown(rest[0].to_string()));
+                index += 1;
+            }
+        }
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment() {
+        let lexicon = Word::defaults().collect::<Vec<_>>();
+
+        let segments = segment("Học sinh đọc sách về máy bay", &lexicon);
+        assert_eq!(
+            segments.iter().map(Segment::text).collect::<Vec<_>>(),
+            vec!["Học sinh", "đọc", "sách", "về", "máy bay"]
+        );
+        assert!(matches!(segments[0], Segment::Known(..)));
+        assert!(matches!(segments[3], Segment::Unknown(..)));
+
+        // Hyphenated and dialect forms are found as well
+        let segments = segment("in-tơ-nét chén", &lexicon);
+        assert_eq!(
+            segments.iter().map(Segment::text).collect::<Vec<_>>(),
+            vec!["in tơ nét", "chén"]
+        );
+    }
+}
@@ -1,8 +1,10 @@
+//! Whole sentences made from phrases.
+
 use crate::grammar::{
     defaults_parser,
     phrase::{NounPhrase, VerbPhrase},
     word::Word,
-    Generate,
+    Derivation, Generate,
 };
 use anyhow::{anyhow, Result};
 use log::debug;
@@ -26,8 +28,34 @@ impl Generate for Sentence {
         )
     }
 
-    fn default_words<'a, R>(
-        rng: &'a mut R,
+    fn derivations(words: &[Word], metadata: Vec<&str>) -> Vec<Derivation> {
+        Self::allowed_structures()
+            .flat_map(|structure| {
+                Derivation::match_structure("sentences.txt", structure, words, |item, words| {
+                    let derivations = match item.to_uppercase().as_str() {
+                        "SUBJECT" | "OBJECT" => NounPhrase::derivations(
+                            words,
+                            metadata
+                                .clone()
+                                .into_iter()
+                                .chain(iter::once(item.to_uppercase().as_str()))
+                                .collect(),
+                        ),
+                        "VP" => VerbPhrase::derivations(words, metadata.clone()),
+                        _ => vec![],
+                    };
+
+                    derivations
+                        .into_iter()
+                        .map(|derivation| (derivation.len, Some(derivation)))
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    fn default_words<R>(
+        rng: &mut R,
         structure: Vec<String>,
         metadata: Vec<&str>,
     ) -> Result<Box<dyn Iterator<Item = Word>>>
@@ -78,7 +106,7 @@ mod tests {
         init();
 
         let allowed = Sentence::allowed_structures().collect::<Vec<_>>();
-        assert!(allowed.len() > 0);
+        assert!(!allowed.is_empty());
 
         Ok(())
     }
@@ -94,4 +122,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        init();
+
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..20 {
+            let mut words = Sentence::generate(&mut rng, vec![])?.collect::<Vec<_>>();
+            let derivation = Sentence::parse(&words, vec![]).unwrap();
+            assert_eq!(derivation.structures()[0].source, "sentences.txt");
+
+            // Moving the end of the sentence to the front is never allowed
+            let len = words.len();
+            words.rotate_left(len - 2);
+            assert!(Sentence::parse(&words, vec![]).is_none());
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,403 @@
+//! The structured lexicon format, words in TOML or JSON.
+//!
+//! The properties are the same as the metadata of the word lines, but a word can have several
+//! meanings and examples and no character has a special meaning. The format is described by the
+//! JSON schema in `src/lexicon.schema.json`:
+//!
+//! ```toml
+//! [[words]]
+//! word = "bát"
+//! class = "common noun"
+//! meanings = ["bowl"]
+//! examples = [{ vietnamese = "Cho tôi một bát phở", english = "Give me a bowl of phở" }]
+//!
+//! [words.properties]
+//! CLASSIFIER = "cái"
+//! OBJECT = true
+//! SOUTHERN = "chén"
+//! ```
+use crate::grammar::{
+    defaults_parser::{self, Metadata},
+    dialect::Dialect,
+    lexicon, syllable,
+    word::{Class, Example, Word},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// The JSON schema of the format, for editors and other tools.
+pub const SCHEMA: &str = include_str!("../lexicon.schema.json");
+
+/// A file with words.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lexicon {
+    /// Where the schema is, editors use it to check the file.
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// The words in the order they're written.
+    #[serde(default)]
+    pub words: Vec<Entry>,
+}
+
+impl Lexicon {
+    /// The words with their classes and properties, fails at the first entry that's not valid.
+    pub fn words(&self) -> Result<Vec<Word>> {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                entry
+                    .to_word()
+                    .with_context(|| format!("Word {} \"{}\" is not valid", index + 1, entry.word))
+            })
+            .collect()
+    }
+
+    /// Write the file as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Write the file as TOML.
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+/// A word in the structured format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    /// How the word is written.
+    pub word: String,
+    /// The name of the class like "common noun" or "verb".
+    pub class: String,
+    /// The main meaning first.
+    #[serde(default)]
+    pub meanings: Vec<String>,
+    /// The metadata of the word lines, `SOUTHERN = chén` becomes `SOUTHERN = "chén"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Property>,
+    /// Sentences that use the word.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Example>,
+}
+
+/// The value of a metadata key.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Property {
+    /// Keys without a value like `OBJECT`, `false` is the same as leaving it out.
+    Flag(bool),
+    /// Keys with a single value like `CLASSIFIER = "cái"`.
+    Value(String),
+    /// The key is repeated for every value, like variants in a dialect.
+    Values(Vec<String>),
+}
+
+impl Entry {
+    /// The entry of a line of a word list like `verbs.txt`.
+    pub fn from_line(list: &str, line: &str) -> Result<Self> {
+        let class = lexicon::class_name(list)
+            .ok_or_else(|| anyhow!("\"{}\" is not the name of a word list", list))?;
+        let (word, metadata, meaning) = defaults_parser::parse_word_line(line);
+
+        let mut meanings = meaning
+            .filter(|meaning| !meaning.is_empty())
+            .map(str::to_string)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut properties = BTreeMap::new();
+        for (key, data) in metadata {
+            if let ("MEANING", Some(data)) = (key, data) {
+                meanings.push(data.to_string());
+                continue;
+            }
+
+            let property = match (properties.remove(key), data) {
+                (None, None) => Property::Flag(true),
+                (None, Some(data)) => Property::Value(data.to_string()),
+                (Some(Property::Value(first)), Some(data)) => {
+                    Property::Values(vec![first, data.to_string()])
+                }
+                (Some(Property::Values(mut values)), Some(data)) => {
+                    values.push(data.to_string());
+                    Property::Values(values)
+                }
+                (Some(_), _) => bail!("\"{}\" has the key {} more than once", word, key),
+            };
+            properties.insert(key.to_string(), property);
+        }
+
+        Ok(Entry {
+            word: word.to_string(),
+            class: class.to_string(),
+            meanings,
+            properties,
+            examples: vec![],
+        })
+    }
+
+    /// The word list the word belongs in, like `verbs.txt`.
+    ///
+    /// The class must be written in full like in the schema, "common noun" and not "noun".
+    pub fn list(&self) -> Result<&'static str> {
+        let class = self.class.parse::<Class>()?;
+        let list = lexicon::file_name(&class)
+            .ok_or_else(|| anyhow!("{}s can't be in the lexicon", class))?;
+        if lexicon::class_name(list) != Some(self.class.as_str()) {
+            bail!("Write the class \"{}\" as \"{}\"", self.class, class);
+        }
+
+        Ok(list)
+    }
+
+    /// The properties as the metadata of a word line.
+    pub fn metadata(&self) -> Metadata<'_> {
+        self.properties
+            .iter()
+            .flat_map(|(key, property)| match property {
+                Property::Flag(true) => vec![(key.as_str(), None)],
+                Property::Flag(false) => vec![],
+                Property::Value(value) => vec![(key.as_str(), Some(value.as_str()))],
+                Property::Values(values) => values
+                    .iter()
+                    .map(|value| (key.as_str(), Some(value.as_str())))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// The entry as a line of its word list, the other meanings are added as "MEANING = cup".
+    ///
+    /// The examples are left out, fails when a part can't be written in a line.
+    pub fn to_line(&self) -> Result<String> {
+        let mut metadata = self.metadata();
+        metadata.extend(
+            self.meanings
+                .iter()
+                .skip(1)
+                .map(|meaning| ("MEANING", Some(meaning.as_str()))),
+        );
+
+        defaults_parser::write_word_line(
+            &self.word,
+            &metadata,
+            self.meanings.first().map(String::as_str),
+        )
+    }
+
+    /// The word with its class and properties.
+    ///
+    /// Fails when a property is unknown or has the wrong type, or a word is not spelled as
+    /// Vietnamese.
+    pub fn to_word(&self) -> Result<Word> {
+        let list = self.list()?;
+        self.check(list)?;
+
+        Word::from_metadata(
+            list,
+            &self.word,
+            &self.metadata(),
+            &self.meanings,
+            self.examples.clone(),
+        )
+    }
+
+    /// Check the spelling and the properties, the same as the lint does for word lines.
+    fn check(&self, list: &str) -> Result<()> {
+        let spelling = |word: &str| {
+            syllable::check(word).with_context(|| format!("{} is not spelled right", word))
+        };
+
+        spelling(&self.word)?;
+        let keys = lexicon::keys(list).ok_or_else(|| anyhow!("{} is not a word list", list))?;
+        for (key, property) in &self.properties {
+            if !keys(key) || key == "MEANING" {
+                bail!("A {} can't have the property {}", self.class, key);
+            }
+
+            let is_dialect = key.parse::<Dialect>().is_ok();
+            let has_value = matches!(key.as_str(), "CLASSIFIER" | "PAST" | "SINO");
+            match property {
+                Property::Flag(_) if key == "CLASSIFIER" => {
+                    bail!("CLASSIFIER must be a word, like \"cái\"")
+                }
+                Property::Flag(_) => (),
+                Property::Value(value) if key == "CLASSIFIER" => spelling(value)?,
+                Property::Value(_) if has_value => (),
+                Property::Value(variant) if is_dialect => spelling(variant)?,
+                Property::Values(variants) if is_dialect => {
+                    for variant in variants {
+                        spelling(variant)?;
+                    }
+                }
+                _ if has_value => bail!("{} must be a single value", key),
+                _ => bail!("{} must be true or false", key),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a file is in the structured format, a TOML or JSON file.
+pub fn is_structured(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("toml") | Some("json")
+    )
+}
+
+/// Parse a TOML or JSON file, the format is known from the extension.
+pub fn parse(path: &Path, data: &str) -> Result<Lexicon> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => Ok(toml::from_str(data)?),
+        Some("json") => Ok(serde_json::from_str(data)?),
+        _ => bail!("{:?} is not a TOML or JSON file", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let toml = indoc! {r##"
+            [[words]]
+            word = "bát"
+            class = "common noun"
+            meanings = ["bowl", "cup #2: for tea"]
+            examples = [{ vietnamese = "Cho tôi một bát phở", english = "Give me a bowl of phở" }]
+
+            [words.properties]
+            CLASSIFIER = "cái"
+            OBJECT = true
+            SINO = "缽"
+            SOUTHERN = ["chén", "tô"]
+        "##};
+        let lexicon = parse(Path::new("words.toml"), toml)?;
+        let words = lexicon.words()?;
+
+        let word = &words[0];
+        assert_eq!(word.to_string(), "bát");
+        assert_eq!(
+            word.meanings().collect::<Vec<_>>(),
+            vec!["bowl", "cup #2: for tea"]
+        );
+        assert_eq!(word.examples()[0].english, "Give me a bowl of phở");
+        assert_eq!(word.variants().len(), 2);
+        assert_eq!(
+            word.classifier().map(|word| word.to_string()),
+            Some("cái".to_string())
+        );
+        assert!(word.etymology().is_some());
+
+        // The same in JSON
+        assert_eq!(
+            parse(Path::new("words.json"), &lexicon.to_json()?)?,
+            lexicon
+        );
+        assert_eq!(
+            parse(Path::new("words.toml"), &lexicon.to_toml()?)?,
+            lexicon
+        );
+
+        assert!(parse(Path::new("words.toml"), "[[words]]\nword = \"bát\"\n").is_err());
+        assert!(parse(Path::new("words.json"), r#"{"words": [], "extra": 1}"#).is_err());
+        assert!(parse(Path::new("words.txt"), "").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_line() -> Result<()> {
+        // All included words can be converted and give the same words back
+        for (list, data) in lexicon::FILES.iter() {
+            for (line, word) in defaults_parser::parse_str(data).zip(Word::parse_file(list, data)?)
+            {
+                let entry = Entry::from_line(list, line)?;
+                assert_eq!(entry.list()?, *list);
+                assert_eq!(entry.to_word()?, word);
+                assert_eq!(Entry::from_line(list, &entry.to_line()?)?, entry);
+            }
+        }
+
+        let entry = Entry::from_line("verbs.txt", "đọc(PAST = read + SINO = 讀): read")?;
+        assert_eq!(entry.to_line()?, "đọc(PAST = read + SINO = 讀): read");
+        assert!(Entry::from_line("lessons.txt", "đọc").is_err());
+
+        let mut entry = Entry::from_line("verbs.txt", "ăn: eat")?;
+        entry.meanings.push("#1".to_string());
+        assert!(entry.to_line().is_err());
+        entry.class = "adverb".to_string();
+        assert!(entry.to_word().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() -> Result<()> {
+        let invalid = |properties: &str| -> Result<String> {
+            let toml = format!(
+                "[[words]]\nword = \"bát\"\nclass = \"common noun\"\n\
+                 [[words]]\nword = \"chén\"\nclass = \"common noun\"\nproperties = {{ {} }}\n",
+                properties
+            );
+            let err = parse(Path::new("words.toml"), &toml)?
+                .words()
+                .err()
+                .ok_or_else(|| anyhow!("{} is valid", properties))?;
+
+            Ok(format!("{:#}", err))
+        };
+
+        assert_eq!(
+            invalid("PAST = true")?,
+            "Word 2 \"chén\" is not valid: A common noun can't have the property PAST"
+        );
+        assert!(invalid("OBJECT = \"yes\"")?.ends_with("OBJECT must be true or false"));
+        assert!(invalid("CLASSIFIER = true")?.ends_with("CLASSIFIER must be a word, like \"cái\""));
+        assert!(invalid("CLASSIFIER = [\"cái\", \"con\"]")?.ends_with("must be a single value"));
+        assert!(invalid("SOUTHERN = \"xyz\"")?.contains("xyz is not spelled right"));
+        assert!(parse(Path::new("words.toml"), "[[words]]\nword = \"chén\"\nclass = \"common noun\"\nproperties = { SOUTHERN = [\"tô\"], OBJECT = true }\n")?.words().is_ok());
+
+        // A word that's not Vietnamese can't be pronounced
+        let entry = Entry::from_line("common_nouns.txt", "xyz: thing")?;
+        assert!(entry.to_word().is_err());
+        // The schema only has the full names of the classes
+        let mut entry = Entry::from_line("common_nouns.txt", "bát: bowl")?;
+        entry.class = "noun".to_string();
+        assert!(entry.to_word().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema() -> Result<()> {
+        let schema: serde_json::Value = serde_json::from_str(SCHEMA)?;
+        let entry = &schema["$defs"]["entry"];
+
+        // The schema describes all fields of an entry
+        let fields = entry["properties"]
+            .as_object()
+            .ok_or_else(|| anyhow!("No properties in the schema"))?;
+        assert_eq!(
+            fields.keys().collect::<Vec<_>>(),
+            vec!["class", "examples", "meanings", "properties", "word"]
+        );
+        assert_eq!(
+            entry["properties"]["class"]["enum"]
+                .as_array()
+                .map(|classes| classes.len()),
+            Some(8)
+        );
+
+        Ok(())
+    }
+}
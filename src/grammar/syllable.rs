@@ -0,0 +1,457 @@
+//! The parts of a syllable and its spelling.
+
+use anyhow::{anyhow, bail, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// All vowels with their toned variants, in the order huyền, sắc, hỏi, ngã, nặng.
+const VOWELS: [(char, [char; 5]); 12] = [
+    ('a', ['à', 'á', 'ả', 'ã', 'ạ']),
+    ('ă', ['ằ', 'ắ', 'ẳ', 'ẵ', 'ặ']),
+    ('â', ['ầ', 'ấ', 'ẩ', 'ẫ', 'ậ']),
+    ('e', ['è', 'é', 'ẻ', 'ẽ', 'ẹ']),
+    ('ê', ['ề', 'ế', 'ể', 'ễ', 'ệ']),
+    ('i', ['ì', 'í', 'ỉ', 'ĩ', 'ị']),
+    ('o', ['ò', 'ó', 'ỏ', 'õ', 'ọ']),
+    ('ô', ['ồ', 'ố', 'ổ', 'ỗ', 'ộ']),
+    ('ơ', ['ờ', 'ớ', 'ở', 'ỡ', 'ợ']),
+    ('u', ['ù', 'ú', 'ủ', 'ũ', 'ụ']),
+    ('ư', ['ừ', 'ứ', 'ử', 'ữ', 'ự']),
+    ('y', ['ỳ', 'ý', 'ỷ', 'ỹ', 'ỵ']),
+];
+
+/// Initial consonants, longest first so they can be matched greedily.
+const INITIALS: [&str; 27] = [
+    "ngh", "ng", "nh", "ch", "gh", "gi", "kh", "ph", "th", "tr", "qu", "b", "c", "d", "đ", "g",
+    "h", "k", "l", "m", "n", "p", "r", "s", "t", "v", "x",
+];
+
+/// Vowel combinations that can form the nucleus of a syllable.
+const NUCLEI: [&str; 22] = [
+    "a", "ă", "â", "e", "ê", "i", "y", "o", "ô", "ơ", "u", "ư", "iê", "yê", "ia", "ya", "uô", "ua",
+    "ươ", "ưa", "oo", "ôô",
+];
+
+/// One of the six tones of Vietnamese.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_phonology#Tone>
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Tone {
+    /// Level tone, no mark.
+    ///
+    /// **Ma**: Ghost.
+    Ngang,
+    /// Falling tone, grave accent.
+    ///
+    /// **Mà**: But.
+    Huyen,
+    /// Rising tone, acute accent.
+    ///
+    /// **Má**: Mother (Southern).
+    Sac,
+    /// Dipping tone, hook above.
+    ///
+    /// **Mả**: Tomb.
+    Hoi,
+    /// Creaky rising tone, tilde.
+    ///
+    /// **Mã**: Horse (Sino-Vietnamese).
+    Nga,
+    /// Heavy tone, dot below.
+    ///
+    /// **Mạ**: Rice seedling.
+    Nang,
+}
+
+impl Tone {
+    /// The Vietnamese name of the tone.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tone::Ngang => "ngang",
+            Tone::Huyen => "huyền",
+            Tone::Sac => "sắc",
+            Tone::Hoi => "hỏi",
+            Tone::Nga => "ngã",
+            Tone::Nang => "nặng",
+        }
+    }
+
+    /// Index in the toned variants of the `VOWELS` table.
+    fn mark_index(self) -> Option<usize> {
+        match self {
+            Tone::Ngang => None,
+            Tone::Huyen => Some(0),
+            Tone::Sac => Some(1),
+            Tone::Hoi => Some(2),
+            Tone::Nga => Some(3),
+            Tone::Nang => Some(4),
+        }
+    }
+
+    /// Get the tone from the index in the `VOWELS` table.
+    fn from_mark_index(index: usize) -> Tone {
+        match index {
+            0 => Tone::Huyen,
+            1 => Tone::Sac,
+            2 => Tone::Hoi,
+            3 => Tone::Nga,
+            _ => Tone::Nang,
+        }
+    }
+}
+
+/// Split a character into its base letter and tone.
+///
+/// Returns `None` for characters that are not Vietnamese letters.
+pub fn split_tone(c: char) -> Option<(char, Tone)> {
+    VOWELS
+        .iter()
+        .find_map(|(base, toned)| {
+            if *base == c {
+                Some((*base, Tone::Ngang))
+            } else {
+                toned
+                    .iter()
+                    .position(|t| *t == c)
+                    .map(|index| (*base, Tone::from_mark_index(index)))
+            }
+        })
+        .or_else(|| {
+            if c.is_ascii_lowercase() || c == 'đ' {
+                Some((c, Tone::Ngang))
+            } else {
+                None
+            }
+        })
+}
+
+/// Put a tone mark on a base vowel.
+pub fn with_tone(c: char, tone: Tone) -> char {
+    match tone.mark_index() {
+        Some(index) => VOWELS
+            .iter()
+            .find(|(base, _)| *base == c)
+            .map(|(_, toned)| toned[index])
+            .unwrap_or(c),
+        None => c,
+    }
+}
+
+/// Whether the (untoned, lowercase) character is a vowel.
+pub fn is_vowel(c: char) -> bool {
+    VOWELS.iter().any(|(base, _)| *base == c)
+}
+
+/// Split a text into the written syllables, ignoring punctuation.
+///
+/// **Máy bay, in-tơ-nét**: máy, bay, in, tơ, nét.
+pub fn split(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|syllable| {
+            syllable.trim_matches(|c: char| c.is_ascii_punctuation() || "“”‘’…".contains(c))
+        })
+        .filter(|syllable| !syllable.is_empty())
+}
+
+/// Fails when a syllable of the text is not a Vietnamese syllable.
+///
+/// **Xyz** fails, **máy bay** doesn't.
+pub fn check(text: &str) -> Result<()> {
+    split(text)
+        .map(str::parse::<Syllable>)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(())
+}
+
+/// Lowercase a text and separate all syllables with a single space.
+///
+/// **Máy-bay**: máy bay.
+pub fn normalize(text: &str) -> String {
+    split(text)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remove all tone marks from a lowercase text.
+///
+/// **Tiếng Việt**: tiêng viêt.
+pub fn strip_tones(text: &str) -> String {
+    text.chars()
+        .map(|c| split_tone(c).map_or(c, |(base, _)| base))
+        .collect()
+}
+
+/// Remove all diacritics from a lowercase text, like it's typed without a Vietnamese keyboard.
+///
+/// **Tiếng Việt, đường**: tieng viet, duong.
+pub fn to_ascii(text: &str) -> String {
+    strip_tones(text)
+        .chars()
+        .map(|c| match c {
+            'ă' | 'â' => 'a',
+            'ê' => 'e',
+            'ô' | 'ơ' => 'o',
+            'ư' => 'u',
+            'đ' => 'd',
+            c => c,
+        })
+        .collect()
+}
+
+/// A single syllable split into it's orthographic parts.
+///
+/// All parts are lowercase and without tone marks, the tone is stored separately.
+///
+/// **Nguyễn**: `ng` + `u` + `yê` + `n` with tone ngã.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_phonology#Syllables>
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Syllable {
+    /// Consonant at the start, empty if the syllable starts with a vowel.
+    pub initial: String,
+    /// Rounded glide before the nucleus, written as `o` or `u`.
+    pub medial: Option<char>,
+    /// The main vowel or diphthong.
+    pub nucleus: String,
+    /// Final consonant or semivowel, empty for open syllables.
+    pub coda: String,
+    /// Tone of the whole syllable.
+    pub tone: Tone,
+    /// Whether the first letter is written as a capital.
+    pub capitalized: bool,
+}
+
+impl Syllable {
+    /// Whether the syllable ends in a stop consonant, which only allows the sắc and nặng tones.
+    pub fn is_checked(&self) -> bool {
+        matches!(self.coda.as_str(), "p" | "t" | "c" | "ch")
+    }
+
+    /// Position in the written rhyme that carries the tone mark.
+    fn tone_position(&self, rhyme: &[char]) -> usize {
+        let offset = if self.medial.is_some() { 1 } else { 0 };
+        let nucleus = self.nucleus.chars().collect::<Vec<_>>();
+
+        // Vowels with a diacritic always get the tone mark, for "ươ" that's the second one
+        if let Some(index) = nucleus
+            .iter()
+            .rposition(|c| matches!(c, 'ă' | 'â' | 'ê' | 'ô' | 'ơ' | 'ư'))
+        {
+            return offset + index;
+        }
+
+        // Open diphthongs like "ia" and "ua" put the mark on the first vowel
+        let index = if nucleus.len() > 1 && self.coda.is_empty() {
+            0
+        } else {
+            nucleus.len() - 1
+        };
+
+        (offset + index).min(rhyme.len() - 1)
+    }
+}
+
+impl FromStr for Syllable {
+    type Err = anyhow::Error;
+
+    fn from_str(syllable: &str) -> Result<Self> {
+        let capitalized = syllable.chars().next().is_some_and(char::is_uppercase);
+
+        // Split the tone from the letters
+        let mut tone = Tone::Ngang;
+        let mut letters = String::new();
+        for c in syllable.to_lowercase().chars() {
+            let (base, letter_tone) =
+                split_tone(c).ok_or_else(|| anyhow!("Invalid letter '{}' in {}", c, syllable))?;
+            if letter_tone != Tone::Ngang {
+                if tone != Tone::Ngang {
+                    bail!("Syllable {} has more than one tone mark", syllable);
+                }
+                tone = letter_tone;
+            }
+            letters.push(base);
+        }
+
+        // Find the initial consonant
+        let initial = INITIALS
+            .iter()
+            .find(|initial| letters.starts_with(*initial))
+            .map(|initial| initial.to_string())
+            .unwrap_or_default();
+        let mut rest = letters[initial.len()..].to_string();
+
+        // The "i" of "gi" is also the vowel when no other vowel follows, "gì" and "giếng"
+        if initial == "gi" && (!rest.chars().any(is_vowel) || rest.starts_with('ê')) {
+            rest.insert(0, 'i');
+        }
+        if initial.is_empty() && rest.is_empty() {
+            bail!("Syllable {} has no letters", syllable);
+        }
+
+        // Find the final consonant or semivowel
+        let coda = if ["ng", "nh", "ch"].iter().any(|coda| rest.ends_with(coda)) {
+            rest[rest.len() - 2..].to_string()
+        } else {
+            let mut chars = rest.chars().rev();
+            match (chars.next(), chars.next()) {
+                (Some(last), _) if matches!(last, 'c' | 'm' | 'n' | 'p' | 't') => last.to_string(),
+                // A semivowel after another vowel, except for the "uy" in "thuỷ"
+                (Some(last), Some(previous))
+                    if matches!(last, 'i' | 'y' | 'o' | 'u')
+                        && is_vowel(previous)
+                        && !(last == 'y' && previous == 'u' && rest.chars().count() == 2) =>
+                {
+                    last.to_string()
+                }
+                _ => String::new(),
+            }
+        };
+        let vowels = rest[..rest.len() - coda.len()].to_string();
+
+        // Find the rounded glide
+        let mut chars = vowels.chars();
+        let medial = match (chars.next(), chars.next()) {
+            (Some('o'), Some('a' | 'ă' | 'e')) => Some('o'),
+            (Some('u'), Some('â' | 'ê' | 'y' | 'ơ')) => Some('u'),
+            _ => None,
+        };
+        let nucleus = vowels[medial.map_or(0, char::len_utf8)..].to_string();
+
+        if !NUCLEI.contains(&nucleus.as_str()) {
+            bail!("Syllable {} has an invalid vowel {}", syllable, vowels);
+        }
+        if initial == "qu" && medial.is_some() {
+            bail!("Syllable {} has a double rounded glide", syllable);
+        }
+
+        let syllable_parts = Syllable {
+            initial,
+            medial,
+            nucleus,
+            coda,
+            tone,
+            capitalized,
+        };
+
+        // Stop consonants can only be combined with the sắc and nặng tones
+        if syllable_parts.is_checked() && !matches!(tone, Tone::Sac | Tone::Nang) {
+            bail!(
+                "Syllable {} ends in a stop but has the {} tone",
+                syllable,
+                tone.name()
+            );
+        }
+
+        Ok(syllable_parts)
+    }
+}
+
+impl Display for Syllable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rhyme = self
+            .medial
+            .into_iter()
+            .chain(self.nucleus.chars())
+            .chain(self.coda.chars())
+            .collect::<Vec<_>>();
+
+        // Put the tone mark on the right vowel
+        let position = self.tone_position(&rhyme);
+        rhyme[position] = with_tone(rhyme[position], self.tone);
+
+        // The "i" of "gi" is written once
+        let initial = if self.initial == "gi" && self.nucleus.starts_with('i') {
+            "g"
+        } else {
+            self.initial.as_str()
+        };
+
+        let syllable = initial.chars().chain(rhyme).collect::<String>();
+        if self.capitalized {
+            let mut chars = syllable.chars();
+            if let Some(first) = chars.next() {
+                write!(f, "{}", first.to_uppercase())?;
+            }
+            write!(f, "{}", chars.as_str())
+        } else {
+            write!(f, "{}", syllable)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let syllable = "Nguyễn".parse::<Syllable>()?;
+        assert_eq!(syllable.initial, "ng");
+        assert_eq!(syllable.medial, Some('u'));
+        assert_eq!(syllable.nucleus, "yê");
+        assert_eq!(syllable.coda, "n");
+        assert_eq!(syllable.tone, Tone::Nga);
+        assert!(syllable.capitalized);
+
+        let syllable = "quốc".parse::<Syllable>()?;
+        assert_eq!(syllable.initial, "qu");
+        assert_eq!(syllable.nucleus, "ô");
+        assert_eq!(syllable.coda, "c");
+        assert_eq!(syllable.tone, Tone::Sac);
+
+        let syllable = "gì".parse::<Syllable>()?;
+        assert_eq!(syllable.initial, "gi");
+        assert_eq!(syllable.nucleus, "i");
+
+        let syllable = "người".parse::<Syllable>()?;
+        assert_eq!(syllable.nucleus, "ươ");
+        assert_eq!(syllable.coda, "i");
+        assert_eq!(syllable.tone, Tone::Huyen);
+
+        let syllable = "thuỷ".parse::<Syllable>()?;
+        assert_eq!(syllable.medial, Some('u'));
+        assert_eq!(syllable.nucleus, "y");
+        assert!(syllable.coda.is_empty());
+
+        let syllable = "ăn".parse::<Syllable>()?;
+        assert!(syllable.initial.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("xyz".parse::<Syllable>().is_err());
+        assert!("mát".parse::<Syllable>().is_ok());
+        assert!("màt".parse::<Syllable>().is_err());
+        assert!("mảá".parse::<Syllable>().is_err());
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split("Máy bay, in-tơ-nét.").collect::<Vec<_>>(),
+            vec!["Máy", "bay", "in", "tơ", "nét"]
+        );
+        assert_eq!(normalize(" Học-Sinh  "), "học sinh");
+        assert_eq!(strip_tones("tiếng việt"), "tiêng viêt");
+        assert_eq!(to_ascii("tiếng việt, đường"), "tieng viet, duong");
+    }
+
+    #[test]
+    fn test_display() -> Result<()> {
+        for word in &[
+            "Nguyễn", "quốc", "gì", "giếng", "người", "hoà", "mía", "của", "chuyện", "Việt", "đọc",
+            "khuỷu",
+        ] {
+            assert_eq!(word.parse::<Syllable>()?.to_string(), *word);
+        }
+
+        // The old tone mark placement is normalized
+        assert_eq!("hòa".parse::<Syllable>()?.to_string(), "hoà");
+
+        Ok(())
+    }
+}
@@ -1,114 +1,363 @@
-use crate::grammar::defaults_parser;
-use anyhow::{anyhow, Result};
+//! Words and their classes.
+
+use crate::grammar::{
+    defaults_parser::{self, Metadata},
+    dialect::Dialect,
+    etymology::Etymology,
+    lexicon, phonetics,
+    syllable::{self, Syllable},
+};
+use anyhow::{anyhow, bail, Result};
 use log::debug;
 use rand::{seq::IteratorRandom, Rng};
-use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 /// A single word.
 ///
 /// **Đi**: Go.
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Word {
     /// The actual word as a string.
     content: String,
     /// Rough translation of the word in English.
     meaning: String,
+    /// Other translations, used less often than the meaning.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    other_meanings: Vec<String>,
+    /// Sentences that show how the word is used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    examples: Vec<Example>,
     /// How this word is classified.
     class: Class,
+    /// The dialect the content belongs to, `None` if it's used everywhere.
+    dialect: Option<Dialect>,
+    /// The same word as used in other dialects.
+    ///
+    /// **Bát**: chén (Southern).
+    variants: Vec<(Dialect, String)>,
+    /// Where the word came from, if known.
+    etymology: Option<Etymology>,
 }
 
 impl Word {
-    /// Parse the included text files and generate a list of words from that.
+    /// The words of the lexicon, the included word lists and the words added to it.
+    ///
+    /// See [`lexicon::words`].
     pub fn defaults() -> impl Iterator<Item = Word> {
-        // Parse the classifiers and add them
-        defaults_parser::parse_str(include_str!("../classifiers.txt"))
+        lexicon::words().iter().cloned()
+    }
+
+    /// Parse a word list, the class of the words is known from the name like `verbs.txt`.
+    pub fn parse_file(name: &str, data: &str) -> Result<Vec<Word>> {
+        let class = Self::class_parser(name)?;
+
+        Ok(defaults_parser::parse_str(data)
             .map(|line| {
-                let (word, _, meaning) = defaults_parser::parse_word_line(line);
+                let (word, metadata, meaning) = defaults_parser::parse_word_line(line);
+                Word::from_parts(word, &metadata, meaning.into_iter(), vec![], class)
+            })
+            .collect())
+    }
+
+    /// Create a word of a word list like `verbs.txt` from its metadata, the first meaning is
+    /// the main one.
+    pub fn from_metadata(
+        list: &str,
+        word: &str,
+        metadata: &Metadata,
+        meanings: &[String],
+        examples: Vec<Example>,
+    ) -> Result<Word> {
+        let class = Self::class_parser(list)?;
+
+        Ok(Word::from_parts(
+            word,
+            metadata,
+            meanings.iter().map(String::as_str),
+            examples,
+            class,
+        ))
+    }
+
+    /// How the class of a word is determined from its metadata, for a word list.
+    fn class_parser(name: &str) -> Result<fn(&Metadata) -> Class> {
+        Ok(match name {
+            "classifiers.txt" => |_| Class::ClassifierNoun(ClassifierNoun {}),
+            "proper_nouns.txt" => |metadata| {
+                Class::ProperNoun(ProperNoun {
+                    is_object: metadata.contains(&("OBJECT", None)),
+                    is_subject: metadata.contains(&("SUBJECT", None)),
+                    is_person: metadata.contains(&("PERSON", None)),
+                    classifier: classifier(metadata),
+                })
+            },
+            "common_nouns.txt" => |metadata| {
+                Class::CommonNoun(CommonNoun {
+                    is_object: metadata.contains(&("OBJECT", None)),
+                    is_subject: metadata.contains(&("SUBJECT", None)),
+                    kind: metadata
+                        .iter()
+                        .find_map(|(key, _)| key.parse().ok())
+                        .unwrap_or(CommonNounKind::Item),
+                    classifier: classifier(metadata),
+                })
+            },
+            "verbs.txt" => |metadata| {
+                Class::Verb(Verb {
+                    past: metadata
+                        .iter()
+                        .find(|(key, _)| *key == "PAST")
+                        .and_then(|(_, data)| data.map(|data| data.to_string())),
+                })
+            },
+            "adjectives.txt" => |metadata| {
+                Class::Adjective(Adjective {
+                    intensifiable: metadata.contains(&("INTENSIVE", None)),
+                })
+            },
+            "tense_markers.txt" => |metadata| {
+                Class::TenseMarker(
+                    metadata
+                        .iter()
+                        .find_map(|(key, _)| key.parse().ok())
+                        .unwrap_or(Tense::Past),
+                )
+            },
+            "demonstratives.txt" => |_| Class::Demonstrative(Demonstrative {}),
+            "interjections.txt" => |_| Class::Interjection,
+            _ => bail!("\"{}\" is not the name of a word list", name),
+        })
+    }
 
-                // Create a word from the parsed line
-                Word {
-                    content: word.to_string(),
-                    meaning: meaning.unwrap_or("").to_string(),
-                    class: Class::ClassifierNoun(ClassifierNoun {}),
+    /// Create a word from the parts of a line with the format "word(metadata): meaning".
+    ///
+    /// The class is determined from the metadata by the passed function, the metadata keys
+    /// shared by all classes are handled here.
+    fn from_parts<'a, M, F>(
+        word: &str,
+        metadata: &Metadata,
+        mut meanings: M,
+        examples: Vec<Example>,
+        class: F,
+    ) -> Word
+    where
+        M: Iterator<Item = &'a str>,
+        F: FnOnce(&Metadata) -> Class,
+    {
+        // "NORTHERN" tags the word itself, "SOUTHERN = chén" adds a variant for the dialect
+        let mut dialect = None;
+        let mut variants = vec![];
+        for (key, data) in metadata.iter() {
+            if let Ok(key_dialect) = key.parse::<Dialect>() {
+                match data {
+                    Some(variant) => variants.push((key_dialect, variant.to_string())),
+                    None => dialect = Some(key_dialect),
                 }
-            })
-            // Parse the proper nouns and add them
-            .chain(
-                defaults_parser::parse_str(include_str!("../proper_nouns.txt")).map(|line| {
-                    let (word, metadata, meaning) = defaults_parser::parse_word_line(line);
+            }
+        }
+        // A word with a variant for one dialect belongs to the other dialect
+        if dialect.is_none() {
+            dialect = variants
+                .first()
+                .map(|(variant_dialect, _)| variant_dialect.other());
+        }
 
-                    // Find the classifier and set the data if it's found
-                    let classifier = metadata
+        Word {
+            content: word.to_string(),
+            meaning: meanings.next().unwrap_or("").to_string(),
+            // "MEANING = cup" adds a meaning to a word line
+            other_meanings: meanings
+                .map(str::to_string)
+                .chain(
+                    metadata
                         .iter()
-                        .find(|(key, data)| key.contains("CLASSIFIER"))
-                        .map(|(_, opt_data)| opt_data.map(|data| data.to_string()))
-                        .flatten();
-
-                    let proper_noun = ProperNoun {
-                        is_object: metadata.contains(&("OBJECT", None)),
-                        is_subject: metadata.contains(&("SUBJECT", None)),
-                        classifier,
-                    };
-
-                    // Create a word from the parsed line
-                    Word {
-                        content: word.to_string(),
-                        meaning: meaning.unwrap_or("").to_string(),
-                        class: Class::ProperNoun(proper_noun),
-                    }
-                }),
-            )
-            // Parse the verbs and add them
-            .chain(
-                defaults_parser::parse_str(include_str!("../verbs.txt")).map(|line| {
-                    let (word, _, meaning) = defaults_parser::parse_word_line(line);
-
-                    // Create a word from the parsed line
-                    Word {
-                        content: word.to_string(),
-                        meaning: meaning.unwrap_or("").to_string(),
-                        class: Class::Verb(Verb {}),
-                    }
-                }),
-            )
-            // Parse the demonstratives and add them
-            .chain(
-                defaults_parser::parse_str(include_str!("../demonstratives.txt")).map(|line| {
-                    let (word, _, meaning) = defaults_parser::parse_word_line(line);
-                    // Create a word from the parsed line
-                    Word {
-                        content: word.to_string(),
-                        meaning: meaning.unwrap_or("").to_string(),
-                        class: Class::Demonstrative(Demonstrative {}),
-                    }
-                }),
-            )
-            // Parse the classifiers and add them
-            .chain(
-                defaults_parser::parse_str(include_str!("../classifiers.txt")).map(|line| {
-                    let (word, _, meaning) = defaults_parser::parse_word_line(line);
-
-                    // Create a word from the parsed line
-                    Word {
-                        content: word.to_string(),
-                        meaning: meaning.unwrap_or("").to_string(),
-                        class: Class::ClassifierNoun(ClassifierNoun {}),
-                    }
-                }),
-            )
+                        .filter(|(key, _)| *key == "MEANING")
+                        .filter_map(|(_, data)| data.map(str::to_string)),
+                )
+                .collect(),
+            examples,
+            class: class(metadata),
+            dialect,
+            variants,
+            etymology: Etymology::from_metadata(metadata),
+        }
+    }
+
+    /// Identifies the word, the same for the word in every dialect.
+    ///
+    /// **Chén**: common noun bát.
+    pub fn id(&self) -> String {
+        let form = self
+            .accepted_forms(None)
+            .into_iter()
+            .min()
+            .unwrap_or(&self.content);
+
+        format!("{} {}", self.class, form)
+    }
+
+    /// Rough translation of the word in English.
+    pub fn meaning(&self) -> &str {
+        &self.meaning
+    }
+
+    /// The meaning followed by the other translations.
+    pub fn meanings(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.meaning.as_str()).chain(self.other_meanings.iter().map(String::as_str))
+    }
+
+    /// Sentences that show how the word is used.
+    pub fn examples(&self) -> &[Example] {
+        &self.examples
+    }
+
+    /// How this word is classified.
+    pub fn class(&self) -> &Class {
+        &self.class
+    }
+
+    /// The same word as used in other dialects.
+    pub fn variants(&self) -> &[(Dialect, String)] {
+        &self.variants
+    }
+
+    /// Where the word came from, if known.
+    pub fn etymology(&self) -> Option<&Etymology> {
+        self.etymology.as_ref()
+    }
+
+    /// The classifier that is used when counting or pointing at this noun.
+    ///
+    /// **Sách**: quyển, quyển sách.
+    pub fn classifier(&self) -> Option<Word> {
+        let classifier = self.classifier_content()?;
+
+        Self::defaults().find(|word| {
+            word.class == Class::ClassifierNoun(ClassifierNoun {}) && word.content == classifier
+        })
+    }
+
+    /// Whether the word is the name of a person.
+    pub fn is_person(&self) -> bool {
+        matches!(
+            self.class,
+            Class::ProperNoun(ProperNoun {
+                is_person: true,
+                ..
+            })
+        )
+    }
+
+    /// Whether the word is a noun with a classifier.
+    pub fn has_classifier(&self) -> bool {
+        self.classifier_content().is_some()
+    }
+
+    /// The classifier of a noun as it's written in the data.
+    fn classifier_content(&self) -> Option<&str> {
+        match &self.class {
+            Class::ProperNoun(ProperNoun { classifier, .. })
+            | Class::CommonNoun(CommonNoun { classifier, .. }) => classifier.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the word has the same origin.
+    pub fn has_origin(&self, origin: &Etymology) -> bool {
+        self.etymology
+            .as_ref()
+            .is_some_and(|etymology| etymology.same_origin(origin))
+    }
+
+    /// The same word as it's used in a dialect.
+    ///
+    /// **Bát**: chén (Southern).
+    pub fn in_dialect(&self, dialect: Dialect) -> Word {
+        match self.variants.iter().find(|(d, _)| *d == dialect) {
+            Some((_, variant)) => Word {
+                content: variant.clone(),
+                dialect: Some(dialect),
+                // Swap the variant with the current content
+                variants: self
+                    .variants
+                    .iter()
+                    .filter(|(d, _)| *d != dialect)
+                    .cloned()
+                    .chain(self.dialect.map(|d| (d, self.content.clone())))
+                    .collect(),
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// All written forms of this word that are accepted as an answer.
+    ///
+    /// When a dialect is passed only the form used in that dialect is accepted, otherwise all
+    /// variants are.
+    pub fn accepted_forms(&self, dialect: Option<Dialect>) -> Vec<&str> {
+        match dialect {
+            Some(dialect) => {
+                let word = self
+                    .variants
+                    .iter()
+                    .find(|(d, _)| *d == dialect)
+                    .map(|(_, variant)| variant.as_str());
+
+                vec![word.unwrap_or(&self.content)]
+            }
+            None => std::iter::once(self.content.as_str())
+                .chain(self.variants.iter().map(|(_, variant)| variant.as_str()))
+                .collect(),
+        }
+    }
+
+    /// Whether the answer is a correct way to write this word.
+    ///
+    /// Case, punctuation and how the syllables of compounds are separated are ignored.
+    pub fn accepts(&self, answer: &str, dialect: Option<Dialect>) -> bool {
+        let answer = syllable::normalize(answer);
+
+        self.accepted_forms(dialect)
+            .into_iter()
+            .any(|form| syllable::normalize(form) == answer)
+    }
+
+    /// The syllables the word is made of, most words are compounds of multiple syllables.
+    ///
+    /// **Máy bay**: máy + bay, airplane.
+    pub fn syllables(&self) -> Result<Vec<Syllable>> {
+        syllable::split(&self.content).map(str::parse).collect()
+    }
+
+    /// Whether the word consists of more than one syllable.
+    pub fn is_compound(&self) -> bool {
+        syllable::split(&self.content).nth(1).is_some()
     }
 
     /// Get a random word belonging to a class.
-    pub fn random_default<'a, R>(rng: &'a mut R, class: Class) -> Result<Word>
+    pub fn random_default<R>(rng: &mut R, class: Class) -> Result<Word>
     where
         R: Rng,
     {
         debug!("Word: {:?}", class);
         Self::defaults()
-            .filter(|word| word.class == class)
+            .filter(|word| word.class.fits(&class))
             .choose(rng)
             .ok_or(anyhow!("Could not get random word with class {:?}", class))
     }
+
+    /// How the word is pronounced in a dialect, in IPA.
+    ///
+    /// **Đọc**: ɗɔk͡p˨˩ (Northern).
+    pub fn pronunciation(&self, dialect: Dialect) -> Result<String> {
+        phonetics::transcribe(&self.content, dialect)
+    }
 }
 
 impl Display for Word {
@@ -117,12 +366,23 @@ impl Display for Word {
     }
 }
 
+/// A sentence that uses a word.
+///
+/// **Cho tôi một bát phở**: Give me a bowl of phở.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Example {
+    /// The sentence in Vietnamese, the word is marked with `**`.
+    pub vietnamese: String,
+    /// The translation.
+    pub english: String,
+}
+
 /// Classification of a word.
 ///
 /// See:
-/// - https://en.wikipedia.org/wiki/Part_of_speech
-/// - https://en.wikipedia.org/wiki/Vietnamese_grammar
-#[derive(Debug, Clone, Hash)]
+/// - <https://en.wikipedia.org/wiki/Part_of_speech>
+/// - <https://en.wikipedia.org/wiki/Vietnamese_grammar>
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Class {
     /// **Ý**: Italy.
     ProperNoun(ProperNoun),
@@ -132,69 +392,245 @@ pub enum Class {
     ClassifierNoun(ClassifierNoun),
     /// Tôi **đi**: I go.
     Verb(Verb),
-    Adjective,
+    /// Cô ấy **đẹp**: She is beautiful.
+    Adjective(Adjective),
+    /// Describes a verb or an adjective.
+    ///
+    /// Cô ấy **rất** đẹp: She is very beautiful.
     Adverb,
+    /// Takes the place of a noun.
+    ///
+    /// **Tôi** đi: I go.
     Pronoun,
+    /// Joins words or sentences.
+    ///
+    /// Tôi **và** anh: Me and you.
     Conjunction,
+    /// Word that stands on its own to answer or react.
+    ///
+    /// **Vâng**: Yes.
     Interjection,
+    /// Says how many or which of a noun.
+    ///
+    /// **Những** con gà: The chickens.
     Determiner,
+    /// Tôi **đã** đi: I went.
+    TenseMarker(Tense),
     /// Ngày **kia**, ngày **kìa**, ngày **kía**, ngày **kịa**, ngày **kĩa**: On and on into the future.
     Demonstrative(Demonstrative),
 }
 
-impl PartialEq for Class {
-    fn eq(&self, other: &Self) -> bool {
+impl Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Class::ProperNoun(proper_noun) => {
-                // Match proper nouns that are both subject or objects
-                if let Class::ProperNoun(other) = other {
-                    (proper_noun.is_object && other.is_object)
-                        || (proper_noun.is_subject && other.is_subject)
-                } else {
-                    false
-                }
+            Class::ProperNoun(_) => write!(f, "proper noun"),
+            Class::CommonNoun(_) => write!(f, "common noun"),
+            Class::ClassifierNoun(_) => write!(f, "classifier"),
+            Class::Verb(_) => write!(f, "verb"),
+            Class::Adjective(_) => write!(f, "adjective"),
+            Class::Adverb => write!(f, "adverb"),
+            Class::Pronoun => write!(f, "pronoun"),
+            Class::Conjunction => write!(f, "conjunction"),
+            Class::Interjection => write!(f, "interjection"),
+            Class::Determiner => write!(f, "determiner"),
+            Class::TenseMarker(_) => write!(f, "tense marker"),
+            Class::Demonstrative(_) => write!(f, "demonstrative"),
+        }
+    }
+}
+
+impl FromStr for Class {
+    type Err = anyhow::Error;
+
+    /// Parse the name of a class like "common noun" or an abbreviation like "n", with the
+    /// defaults of the class.
+    fn from_str(class: &str) -> Result<Self> {
+        Ok(match class.trim().to_lowercase().as_str() {
+            "proper noun" | "name" => Class::ProperNoun(ProperNoun::default()),
+            "common noun" | "noun" | "n" => Class::CommonNoun(CommonNoun {
+                is_subject: false,
+                is_object: false,
+                kind: CommonNounKind::Item,
+                classifier: None,
+            }),
+            "classifier" | "clf" => Class::ClassifierNoun(ClassifierNoun {}),
+            "verb" | "v" => Class::Verb(Verb::default()),
+            "adjective" | "adj" | "a" => Class::Adjective(Adjective::default()),
+            "adverb" | "adv" => Class::Adverb,
+            "pronoun" | "pron" => Class::Pronoun,
+            "conjunction" | "conj" => Class::Conjunction,
+            "interjection" | "intj" => Class::Interjection,
+            "determiner" | "det" => Class::Determiner,
+            "tense marker" => Class::TenseMarker(Tense::Past),
+            "demonstrative" => Class::Demonstrative(Demonstrative {}),
+            _ => bail!("Unrecognized class {}", class),
+        })
+    }
+}
+
+impl Class {
+    /// Whether a word of this class can fill a slot of a sentence, nouns must have the role of
+    /// the slot.
+    ///
+    /// **Tôi** can fill the subject slot of "Tôi đọc sách", but **sách** only the object slot.
+    pub fn fits(&self, slot: &Class) -> bool {
+        match (self, slot) {
+            (Class::ProperNoun(noun), Class::ProperNoun(slot)) => {
+                (noun.is_object && slot.is_object) || (noun.is_subject && slot.is_subject)
+            }
+            (Class::CommonNoun(noun), Class::CommonNoun(slot)) => {
+                (noun.is_object && slot.is_object) || (noun.is_subject && slot.is_subject)
             }
-            // Only compare the variants, not the value
-            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+            _ => self == slot,
         }
     }
 }
 
+impl PartialEq for Class {
+    fn eq(&self, other: &Self) -> bool {
+        // Only compare the variants, not the value
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
 impl Eq for Class {}
 
+impl Hash for Class {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Only hash the variant, to be consistent with the equality check
+        std::mem::discriminant(self).hash(state);
+    }
+}
+
 /// Action, occurance or state of being.
 ///
 /// Tôi **đi**: I go.
 ///
-/// See: https://yourvietnamese.com/learn-vietnamese/vietnamese-verbs/
-#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
-pub struct Verb {}
+/// See: <https://yourvietnamese.com/learn-vietnamese/vietnamese-verbs/>
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Verb {
+    /// The English past tense of the meaning if it's irregular.
+    pub past: Option<String>,
+}
 
-/// Common noun subclasses.
+/// When an action happens, verbs don't change with the tense but are marked by a word in front.
+///
+/// Tôi **sẽ** đi: I will go.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_grammar#Tense_and_aspect>
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Tense {
+    /// **Đã**.
+    Past,
+    /// **Đang**, still going on.
+    Progressive,
+    /// **Sẽ**.
+    Future,
+}
+
+impl FromStr for Tense {
+    type Err = anyhow::Error;
+
+    fn from_str(tense: &str) -> Result<Self> {
+        match tense {
+            "PAST" => Ok(Tense::Past),
+            "PROGRESSIVE" => Ok(Tense::Progressive),
+            "FUTURE" => Ok(Tense::Future),
+            _ => Err(anyhow!("Unrecognized tense {}", tense)),
+        }
+    }
+}
+
+/// Describes a noun, behaves like a verb.
+///
+/// Cô ấy **đẹp**: She is beautiful.
+///
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_grammar#Adjectives>
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Adjective {
+    /// Whether the word can be intensified by a triple reduplication, like **sạch** sành sanh.
+    pub intensifiable: bool,
+}
+
+/// Not a name.
 ///
 /// **Gái**: Girl.
 ///
-/// See: https://en.wikipedia.org/wiki/Vietnamese_grammar#Nouns_and_noun_phrases
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-pub enum CommonNoun {
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_grammar#Nouns_and_noun_phrases>
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct CommonNoun {
+    /// Whether it can be the subject of a sentence.
+    pub is_subject: bool,
+    /// Whether it can be the object of a verb.
+    pub is_object: bool,
+    /// What the noun refers to, which decides how it's counted.
+    pub kind: CommonNounKind,
+    /// The classifier used when counting it, like **cái** for **bát**.
+    pub classifier: Option<String>,
+}
+
+/// Common noun subclasses.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CommonNounKind {
+    /// Things that can be counted one by one.
     Item,
+    /// A group of things.
     Collective,
     /// Or measure.
     Unit,
+    /// Things that can't be counted, like water.
     Mass,
+    /// A period of time.
     Time,
+    /// Ideas and feelings.
     Abstract,
 }
 
+impl FromStr for CommonNounKind {
+    type Err = anyhow::Error;
+
+    fn from_str(subclass: &str) -> Result<Self> {
+        match subclass {
+            "ITEM" => Ok(CommonNounKind::Item),
+            "COLLECTIVE" => Ok(CommonNounKind::Collective),
+            "UNIT" => Ok(CommonNounKind::Unit),
+            "MASS" => Ok(CommonNounKind::Mass),
+            "TIME" => Ok(CommonNounKind::Time),
+            "ABSTRACT" => Ok(CommonNounKind::Abstract),
+            _ => Err(anyhow!("Unrecognized common noun subclass {}", subclass)),
+        }
+    }
+}
+
+impl CommonNounKind {
+    /// The metadata key of the subclass, the reverse of parsing it.
+    pub fn key(&self) -> &'static str {
+        match self {
+            CommonNounKind::Item => "ITEM",
+            CommonNounKind::Collective => "COLLECTIVE",
+            CommonNounKind::Unit => "UNIT",
+            CommonNounKind::Mass => "MASS",
+            CommonNounKind::Time => "TIME",
+            CommonNounKind::Abstract => "ABSTRACT",
+        }
+    }
+}
+
 /// Usually names.
 ///
 /// **Ý**: Italy.
 ///
-/// See: https://en.wikipedia.org/wiki/Vietnamese_grammar#Nouns_and_noun_phrases
-#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_grammar#Nouns_and_noun_phrases>
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ProperNoun {
+    /// Whether it can be the subject of a sentence.
     pub is_subject: bool,
+    /// Whether it can be the object of a verb.
     pub is_object: bool,
+    /// Whether it's the name of a person, which isn't translated.
+    #[serde(default)]
+    pub is_person: bool,
+    /// The classifier used when counting it.
     pub classifier: Option<String>,
 }
 
@@ -204,19 +640,27 @@ pub struct ProperNoun {
 /// - Bán cho tôi bốn **con** gà: Sell me four chickens.
 ///
 /// See:
-/// - https://en.wikipedia.org/wiki/Vietnamese_grammar#Classifier_position
-/// - https://en.wikipedia.org/wiki/Classifier_(linguistics)
-#[derive(Debug, Default, Clone, PartialEq, Hash)]
+/// - <https://en.wikipedia.org/wiki/Vietnamese_grammar#Classifier_position>
+/// - <https://en.wikipedia.org/wiki/Classifier_(linguistics)>
+#[derive(Debug, Default, Clone, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ClassifierNoun {}
 
 /// Noun modifier.
 ///
 /// **Đây** đi chợ, **đấy** có đi không: I'm going to the market, what about you?
 ///
-/// See: https://en.wikipedia.org/wiki/Vietnamese_grammar#Demonstratives
-#[derive(Debug, Default, Clone, PartialEq, Hash)]
+/// See: <https://en.wikipedia.org/wiki/Vietnamese_grammar#Demonstratives>
+#[derive(Debug, Default, Clone, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Demonstrative {}
 
+/// Find the classifier in the metadata, "CLASSIFIER = con".
+fn classifier(metadata: &Metadata) -> Option<String> {
+    metadata
+        .iter()
+        .find(|(key, _)| *key == "CLASSIFIER")
+        .and_then(|(_, data)| data.map(|data| data.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,4 +675,78 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_dialect() -> Result<()> {
+        let word = Word::defaults()
+            .find(|word| word.content == "bát")
+            .ok_or_else(|| anyhow!("Word not found"))?;
+        assert_eq!(word.dialect, Some(Dialect::Northern));
+
+        let southern = word.in_dialect(Dialect::Southern);
+        assert_eq!(southern.to_string(), "chén");
+        assert_eq!(southern.in_dialect(Dialect::Northern).to_string(), "bát");
+
+        assert_eq!(word.id(), southern.id());
+
+        assert!(word.accepts("chén", None));
+        assert!(word.accepts("Bát ", Some(Dialect::Northern)));
+        assert!(!word.accepts("chén", Some(Dialect::Northern)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_classifier() -> Result<()> {
+        for word in Word::defaults() {
+            let classifier = match word.class() {
+                Class::ProperNoun(ProperNoun { classifier, .. })
+                | Class::CommonNoun(CommonNoun { classifier, .. }) => classifier.clone(),
+                _ => None,
+            };
+
+            // Every classifier used by a noun must be a known classifier
+            assert_eq!(
+                classifier,
+                word.classifier().map(|classifier| classifier.to_string())
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_class() -> Result<()> {
+        let noun = "common noun".parse::<Class>()?;
+        let object = Class::CommonNoun(CommonNoun {
+            is_subject: false,
+            is_object: true,
+            kind: CommonNounKind::Item,
+            classifier: None,
+        });
+
+        // A noun without a role is still the same class
+        assert_eq!(noun, noun.clone());
+        assert_eq!(noun, object);
+        assert_ne!(noun, "proper noun".parse()?);
+
+        // But it can't fill a slot
+        assert!(object.fits(&object));
+        assert!(!noun.fits(&object));
+        assert!(!object.fits(&"proper noun".parse()?));
+        assert!(Class::Interjection.fits(&Class::Interjection));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pronunciation() -> Result<()> {
+        // All default words must be valid syllables
+        for word in Word::defaults() {
+            word.pronunciation(Dialect::Northern)?;
+            word.pronunciation(Dialect::Southern)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,124 @@
+use crate::gui::{
+    screen::{self, Frame, Length, Screen, Status},
+    widgets::{List, Text},
+};
+use anyhow::Result;
+use termion::{
+    color::{Cyan, Fg, Reset},
+    event::Key,
+};
+
+/// Let the user put the items in order, returns the indices in the chosen order.
+///
+/// Items are picked with the arrow keys and enter, or by typing their number. Backspace puts
+/// the last picked item back.
+pub fn arrange(title: &str, items: &[&str]) -> Result<Option<Vec<usize>>> {
+    screen::run(&mut Arrange {
+        title,
+        items,
+        order: vec![],
+        selected: 0,
+    })
+}
+
+/// The items that are placed with the remaining items below them.
+struct Arrange<'a> {
+    title: &'a str,
+    items: &'a [&'a str],
+    order: Vec<usize>,
+    selected: usize,
+}
+
+impl Arrange<'_> {
+    /// The indices of the items that aren't placed yet.
+    fn remaining(&self) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|index| !self.order.contains(index))
+            .collect()
+    }
+}
+
+impl Screen for Arrange<'_> {
+    type Output = Option<Vec<usize>>;
+
+    fn draw(&self, frame: &mut Frame) {
+        let remaining = self.remaining();
+        let hint = if remaining.is_empty() {
+            "Press enter to check, backspace to undo."
+        } else {
+            "Pick the next word, backspace to undo."
+        };
+        let area = frame.page(self.title, hint);
+        let rows = area.rows(&[Length::Fixed(2), Length::Fill]);
+
+        // The items that are already placed
+        let sentence = self
+            .order
+            .iter()
+            .map(|index| self.items[*index])
+            .collect::<Vec<_>>()
+            .join(" ");
+        frame.render(
+            &Text::new(&format!("> {}{}{}", Fg(Cyan), sentence, Fg(Reset))),
+            rows[0],
+        );
+
+        let options = remaining
+            .iter()
+            .map(|index| self.items[*index].to_string())
+            .collect::<Vec<_>>();
+        frame.render(
+            // The number is the key that can be pressed to pick the item
+            &List::new(&options, self.selected)
+                .numbered(remaining.iter().map(|index| index + 1).collect()),
+            rows[1],
+        );
+    }
+
+    fn handle(&mut self, key: Key) -> Status<Option<Vec<usize>>> {
+        let remaining = self.remaining();
+
+        match key {
+            Key::Up | Key::Left => {
+                self.selected = self
+                    .selected
+                    .checked_sub(1)
+                    .unwrap_or(remaining.len().max(1) - 1);
+            }
+            Key::Down | Key::Right => {
+                self.selected += 1;
+                if self.selected >= remaining.len() {
+                    // Wrap the cursor around
+                    self.selected = 0;
+                }
+            }
+            Key::Char('\n') | Key::Char(' ') => {
+                if remaining.is_empty() {
+                    return Status::Done(Some(self.order.clone()));
+                }
+                self.order
+                    .push(remaining[self.selected.min(remaining.len() - 1)]);
+                self.selected = 0;
+            }
+            Key::Char(c) => {
+                // Pick the item with the typed number
+                if let Some(index) = c
+                    .to_digit(10)
+                    .and_then(|digit| (digit as usize).checked_sub(1))
+                {
+                    if remaining.contains(&index) {
+                        self.order.push(index);
+                        self.selected = 0;
+                    }
+                }
+            }
+            Key::Backspace => {
+                self.order.pop();
+            }
+            Key::Esc | Key::Ctrl('c') => return Status::Done(None),
+            _ => (),
+        }
+
+        Status::Running
+    }
+}
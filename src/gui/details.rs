@@ -0,0 +1,67 @@
+use crate::gui::{choose, message};
+use anyhow::Result;
+use termion::color::{Cyan, Fg, Reset};
+use tieng_viet::{
+    grammar::{etymology::Etymology, word::Word},
+    settings::Settings,
+};
+
+/// Let the user pick words, filtered by origin, and show their details.
+pub fn browse_words(settings: &Settings) -> Result<()> {
+    let origins = Etymology::all();
+    let filters = std::iter::once("All words".to_string())
+        .chain(origins.iter().map(|origin| origin.to_string()))
+        .collect::<Vec<_>>();
+
+    loop {
+        let origin = match choose("Show words with origin:", &filters)?.index() {
+            Some(0) => None,
+            Some(index) => Some(&origins[index - 1]),
+            None => return Ok(()),
+        };
+
+        let words = Word::defaults()
+            .filter(|word| origin.is_none_or(|origin| word.has_origin(origin)))
+            .collect::<Vec<_>>();
+        let options = words
+            .iter()
+            .map(|word| format!("{}: {}", word, word.meaning()))
+            .collect::<Vec<_>>();
+
+        if let Some(index) = choose("Select a word:", &options)?.index() {
+            word_details(&words[index], settings)?;
+        }
+    }
+}
+
+/// Show everything known about a word until enter is pressed.
+fn word_details(word: &Word, settings: &Settings) -> Result<()> {
+    let mut text = format!(
+        "Meaning: {}\nClass: {}\n",
+        word.meanings().collect::<Vec<_>>().join(", "),
+        word.class()
+    );
+    for (dialect, variant) in word.variants() {
+        text.push_str(&format!("{}: {}\n", dialect, variant));
+    }
+    if let Some(etymology) = word.etymology() {
+        text.push_str(&format!("Origin: {}\n", etymology));
+    }
+    for example in word.examples() {
+        text.push_str(&format!("\n{}\n{}\n", example.vietnamese, example.english));
+    }
+
+    message(
+        &format!(
+            "{}{}{} [{}]",
+            Fg(Cyan),
+            word,
+            Fg(Reset),
+            word.pronunciation(settings.dialect)?
+        ),
+        &text,
+        "Press enter to go back.",
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::gui::{choose, menu, message};
+use anyhow::Result;
+use termion::color::{Cyan, Fg, Reset};
+use tieng_viet::help::{self, Topic};
+
+/// What the keys do on every screen.
+const KEYS: [(&str, &[(&str, &str)]); 5] = [
+    (
+        "Menus",
+        &[
+            ("Up/Down, j/k", "Move the selection"),
+            ("Home/End", "Go to the first or last option"),
+            ("PgUp/PgDown", "Scroll a page"),
+            ("Enter", "Select"),
+            ("1-9", "Select an option by its number"),
+            ("/", "Filter the options by typing"),
+            ("Mouse", "Click an option, scroll with the wheel"),
+            ("Escape", "Go back"),
+            ("Ctrl-C", "Quit from the main menu"),
+        ],
+    ),
+    (
+        "Typing an answer",
+        &[
+            ("Enter", "Answer"),
+            ("Backspace", "Remove a letter"),
+            ("Escape", "Stop"),
+        ],
+    ),
+    (
+        "Putting words in order",
+        &[
+            ("Arrows", "Move the selection"),
+            ("Enter/Space", "Pick the selected word"),
+            ("1-9", "Pick a word by its number"),
+            ("Backspace", "Put the last word back"),
+            ("Escape", "Stop"),
+        ],
+    ),
+    (
+        "After answering",
+        &[
+            ("Enter/Space", "Next exercise"),
+            ("H/?", "Explain the grammar of the exercise"),
+            ("Escape/Q", "Stop"),
+        ],
+    ),
+    (
+        "Other screens",
+        &[
+            ("Up/Down, j/k", "Scroll"),
+            ("Enter/Space", "Go back"),
+            ("Ctrl-Z", "Suspend"),
+        ],
+    ),
+];
+
+/// Let the user read the key reference or browse the grammar reference.
+pub fn show_help() -> Result<()> {
+    loop {
+        match menu("Help:", &["Keys", "Word classes", "Structures", "Back"])?.index() {
+            Some(0) => show_keys()?,
+            Some(1) => show_topics("Word classes:", &help::classes())?,
+            Some(2) => show_topics("Structures:", &help::structures())?,
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Let the user pick one of the topics and show it.
+pub fn show_topics(title: &str, topics: &[Topic]) -> Result<()> {
+    if topics.is_empty() {
+        message(
+            title,
+            "There's no explanation for this.",
+            "Press enter to go back.",
+        )?;
+
+        return Ok(());
+    }
+
+    let options = topics
+        .iter()
+        .map(|topic| topic.title.clone())
+        .chain(std::iter::once("Back".to_string()))
+        .collect::<Vec<_>>();
+
+    loop {
+        match choose(title, &options)?.index() {
+            Some(index) if index < topics.len() => show_topic(&topics[index])?,
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Show the explanation of a topic, with the words in the examples highlighted.
+fn show_topic(topic: &Topic) -> Result<()> {
+    // Every second part is between `**`
+    let text = topic
+        .text
+        .split("**")
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                format!("{}{}{}", Fg(Cyan), part, Fg(Reset))
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<String>();
+    message(&topic.title, &text, "Press enter to go back.")?;
+
+    Ok(())
+}
+
+/// Show what the keys do.
+fn show_keys() -> Result<()> {
+    let text = KEYS
+        .iter()
+        .map(|(screen, keys)| {
+            let keys = keys
+                .iter()
+                .map(|(key, action)| format!("  {}{:12}{} {}\n", Fg(Cyan), key, Fg(Reset), action))
+                .collect::<String>();
+
+            format!("{}\n{}", screen, keys)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    message("Keys", &text, "Press enter to go back.")?;
+
+    Ok(())
+}
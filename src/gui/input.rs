@@ -0,0 +1,146 @@
+use crate::gui::{
+    screen::{self, Frame, Screen, Status},
+    widgets::{Dialog, Text, TextInput},
+};
+use anyhow::Result;
+use std::cell::Cell;
+use termion::event::{Key, MouseButton, MouseEvent};
+
+/// Let the user type a line of text, returns `None` when cancelled.
+pub fn input(title: &str) -> Result<Option<String>> {
+    screen::run(&mut Input {
+        title,
+        text: String::new(),
+    })
+}
+
+/// Show a text until the user presses enter to continue, returns `false` when escape is pressed.
+///
+/// Text that doesn't fit can be scrolled.
+pub fn message(title: &str, text: &str, hint: &str) -> Result<bool> {
+    screen::run(&mut Message {
+        title,
+        text: Text::new(text),
+        hint,
+        offset: 0,
+        height: Cell::new(0),
+    })
+}
+
+/// Show a message in a box until a key is pressed.
+pub fn dialog(title: &str, text: &str) -> Result<()> {
+    screen::run(&mut Popup { title, text })
+}
+
+/// A title with a line of text to type below it.
+struct Input<'a> {
+    title: &'a str,
+    text: String,
+}
+
+impl Screen for Input<'_> {
+    type Output = Option<String>;
+
+    fn draw(&self, frame: &mut Frame) {
+        let area = frame.page(self.title, "Enter to answer, escape to cancel.");
+        frame.render(&TextInput::new(&self.text), area);
+    }
+
+    fn handle(&mut self, key: Key) -> Status<Option<String>> {
+        match key {
+            Key::Char('\n') => return Status::Done(Some(self.text.clone())),
+            Key::Char(c) => self.text.push(c),
+            Key::Backspace => {
+                self.text.pop();
+            }
+            Key::Esc | Key::Ctrl('c') => return Status::Done(None),
+            _ => (),
+        }
+
+        Status::Running
+    }
+}
+
+/// A page of text.
+struct Message<'a> {
+    title: &'a str,
+    text: Text,
+    hint: &'a str,
+    /// The first line that's shown.
+    offset: u16,
+    /// How many lines fit, to know how far it can be scrolled.
+    height: Cell<u16>,
+}
+
+impl Message<'_> {
+    /// Scroll the text up or down, not further than the last line.
+    fn scroll(&mut self, lines: i32) {
+        let max = self.text.height().saturating_sub(self.height.get());
+        self.offset = (self.offset as i32 + lines).clamp(0, max as i32) as u16;
+    }
+}
+
+impl Screen for Message<'_> {
+    type Output = bool;
+
+    fn draw(&self, frame: &mut Frame) {
+        let area = frame.page(self.title, self.hint);
+        frame.render(&self.text.scrolled(self.offset), area);
+        self.height.set(area.height);
+    }
+
+    fn handle(&mut self, key: Key) -> Status<bool> {
+        let page = self.height.get().max(1) as i32;
+        match key {
+            Key::Up | Key::Char('k') => self.scroll(-1),
+            Key::Down | Key::Char('j') => self.scroll(1),
+            Key::PageUp => self.scroll(-page),
+            Key::PageDown => self.scroll(page),
+            Key::Char('\n') | Key::Char(' ') => return Status::Done(true),
+            Key::Esc | Key::Ctrl('c') | Key::Char('q') => return Status::Done(false),
+            _ => (),
+        }
+
+        Status::Running
+    }
+
+    fn mouse(&mut self, event: MouseEvent) -> Status<bool> {
+        match event {
+            MouseEvent::Press(MouseButton::WheelUp, _, _) => self.scroll(-1),
+            MouseEvent::Press(MouseButton::WheelDown, _, _) => self.scroll(1),
+            _ => (),
+        }
+
+        Status::Running
+    }
+}
+
+/// A message in a box.
+struct Popup<'a> {
+    title: &'a str,
+    text: &'a str,
+}
+
+impl Screen for Popup<'_> {
+    type Output = ();
+
+    fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+        frame.render(
+            &Dialog::new(
+                self.title,
+                &format!("{}\n\nPress enter to go back.", self.text),
+            ),
+            area,
+        );
+    }
+
+    fn handle(&mut self, key: Key) -> Status<()> {
+        match key {
+            Key::Char('\n') | Key::Char(' ') | Key::Esc | Key::Ctrl('c') | Key::Char('q') => {
+                Status::Done(())
+            }
+            _ => Status::Running,
+        }
+    }
+}
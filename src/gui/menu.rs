@@ -1,73 +1,251 @@
-use anyhow::{anyhow, Result};
-use std::io::{stdin, stdout, Write};
-use termion::{
-    color::{Cyan, Fg, Reset},
-    cursor::{Restore, Save},
-    event::Key,
-    input::TermRead,
+use crate::gui::{
+    screen::{self, Frame, Length, Rect, Screen, Status},
+    widgets::{List, Text},
 };
+use anyhow::Result;
+use std::cell::Cell;
+use termion::event::{Key, MouseButton, MouseEvent};
+use tieng_viet::grammar::syllable;
 
-/// Draw a menu from an array of options, and return the index selected.
-pub fn menu(options: &[&str]) -> Result<Option<usize>> {
-    let mut selected = 0;
+/// What the user did in a menu.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Selection {
+    /// An option was chosen, by its index in the options.
+    Chosen(usize),
+    /// Escape was pressed to go back.
+    Back,
+    /// Ctrl-C was pressed, the main menu stops the program.
+    Quit,
+}
 
-    draw_menu(options, selected)?;
+impl Selection {
+    /// The index of the chosen option, `None` when going back or quitting.
+    pub fn index(self) -> Option<usize> {
+        match self {
+            Selection::Chosen(index) => Some(index),
+            Selection::Back | Selection::Quit => None,
+        }
+    }
+}
 
-    // Handle keyboard events.
-    for c in stdin().keys() {
-        match c.expect("Could not get key from stdin for menu") {
-            Key::Up => {
-                if selected == 0 {
-                    // Wrap the cursor around
-                    selected = options.len();
-                }
-                selected -= 1;
+/// Ask a multiple choice question, and return the chosen option.
+pub fn choose(title: &str, options: &[String]) -> Result<Selection> {
+    screen::run(&mut Menu {
+        title,
+        options,
+        selected: 0,
+        number: String::new(),
+        filter: None,
+        list_area: Cell::new(None),
+    })
+}
+
+/// Draw a menu from an array of options, and return the option selected.
+pub fn menu(title: &str, options: &[&str]) -> Result<Selection> {
+    choose(
+        title,
+        &options
+            .iter()
+            .map(|option| option.to_string())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// A title with options below it.
+///
+/// Options are picked with the arrow keys, j and k, the mouse or by typing their number. Typing
+/// '/' filters the options by the text typed after it.
+struct Menu<'a> {
+    title: &'a str,
+    options: &'a [String],
+    /// Index in the visible options.
+    selected: usize,
+    /// The digits typed so far.
+    number: String,
+    /// The text typed after '/', `None` when not filtering.
+    filter: Option<String>,
+    /// Where the options were drawn, to know which one is clicked.
+    list_area: Cell<Option<Rect>>,
+}
+
+impl Menu<'_> {
+    /// The indices of the options that match the filter, diacritics don't have to be typed.
+    fn visible(&self) -> Vec<usize> {
+        let filter = syllable::to_ascii(&self.filter.as_deref().unwrap_or("").to_lowercase());
+
+        (0..self.options.len())
+            .filter(|index| {
+                syllable::to_ascii(&self.options[*index].to_lowercase()).contains(&filter)
+            })
+            .collect()
+    }
+
+    /// Choose the selected option.
+    fn choose(&self) -> Status<Selection> {
+        match self.visible().get(self.selected) {
+            Some(index) => Status::Done(Selection::Chosen(*index)),
+            // Nothing matches the filter
+            None => Status::Running,
+        }
+    }
+
+    /// Move the selection, it wraps around at the ends.
+    fn select_next(&mut self, forward: bool) {
+        let len = self.visible().len().max(1);
+        self.selected = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+
+    /// Select the option with the typed number, it's chosen when no other number starts with it.
+    fn type_digit(&mut self, digit: char) -> Status<Selection> {
+        let options = self.options.len();
+        let valid = |number: &str| {
+            number
+                .parse::<usize>()
+                .ok()
+                .filter(|number| (1..=options).contains(number))
+        };
+
+        self.number.push(digit);
+        if valid(&self.number).is_none() {
+            // Start again with the digit
+            self.number = digit.to_string();
+        }
+        let number = match valid(&self.number) {
+            Some(number) => number,
+            None => {
+                self.number.clear();
+                return Status::Running;
             }
-            Key::Down => {
-                selected += 1;
-                if selected == options.len() {
-                    // Wrap the cursor around
-                    selected = 0;
-                }
+        };
+
+        if let Some(position) = self.visible().iter().position(|index| *index == number - 1) {
+            self.selected = position;
+        }
+        if number * 10 > options {
+            self.number.clear();
+            return Status::Done(Selection::Chosen(number - 1));
+        }
+
+        Status::Running
+    }
+
+    /// How many options are shown at once.
+    fn page(&self) -> usize {
+        self.list_area
+            .get()
+            .map_or(1, |area| area.height.max(1) as usize)
+    }
+
+    /// Handle typing the filter, returns whether the key was used.
+    fn type_filter(&mut self, key: Key) -> bool {
+        let filter = match &mut self.filter {
+            Some(filter) => filter,
+            None => return false,
+        };
+
+        match key {
+            Key::Char(c) if c != '\n' => filter.push(c),
+            Key::Backspace => {
+                filter.pop();
+            }
+            Key::Esc => self.filter = None,
+            _ => return false,
+        }
+        self.selected = 0;
+
+        true
+    }
+}
+
+impl Screen for Menu<'_> {
+    type Output = Selection;
+
+    fn draw(&self, frame: &mut Frame) {
+        let hint = if self.filter.is_some() {
+            "Type to filter, enter to select, escape to stop filtering."
+        } else {
+            "j/k or arrows to move, enter or a number to select, / to filter, esc to go back."
+        };
+        let area = frame.page(self.title, hint);
+
+        let area = match &self.filter {
+            Some(filter) => {
+                let rows = area.rows(&[Length::Fixed(2), Length::Fill]);
+                frame.render(&Text::new(&format!("/{}", filter)), rows[0]);
+                frame.set_cursor(rows[0].x + 1 + filter.chars().count() as u16, rows[0].y);
+
+                rows[1]
             }
-            Key::Char('\n') => {
-                return Ok(Some(selected));
+            None => area,
+        };
+
+        let visible = self.visible();
+        let options = visible
+            .iter()
+            .map(|index| self.options[*index].clone())
+            .collect::<Vec<_>>();
+        frame.render(
+            &List::new(&options, self.selected)
+                .numbered(visible.iter().map(|index| index + 1).collect()),
+            area,
+        );
+        self.list_area.set(Some(area));
+    }
+
+    fn handle(&mut self, key: Key) -> Status<Selection> {
+        if self.type_filter(key) {
+            return Status::Running;
+        }
+        if let Key::Char(digit @ '0'..='9') = key {
+            return self.type_digit(digit);
+        }
+        self.number.clear();
+
+        let len = self.visible().len();
+        match key {
+            Key::Up | Key::Char('k') => self.select_next(false),
+            Key::Down | Key::Char('j') => self.select_next(true),
+            Key::Home | Key::Char('g') => self.selected = 0,
+            Key::End | Key::Char('G') => self.selected = len.saturating_sub(1),
+            Key::PageUp => self.selected = self.selected.saturating_sub(self.page()),
+            Key::PageDown => {
+                self.selected = (self.selected + self.page()).min(len.saturating_sub(1))
+            }
+            Key::Char('/') => {
+                self.filter = Some(String::new());
+                self.selected = 0;
             }
-            Key::Ctrl('c') => return Ok(None),
+            Key::Char('\n') => return self.choose(),
+            Key::Esc => return Status::Done(Selection::Back),
+            Key::Ctrl('c') => return Status::Done(Selection::Quit),
             _ => (),
         }
 
-        draw_menu(options, selected)?;
+        Status::Running
     }
 
-    Err(anyhow!("Stdin key capture returned prematurely"))
-}
+    fn mouse(&mut self, event: MouseEvent) -> Status<Selection> {
+        match event {
+            MouseEvent::Press(MouseButton::WheelUp, _, _) => self.select_next(false),
+            MouseEvent::Press(MouseButton::WheelDown, _, _) => self.select_next(true),
+            MouseEvent::Press(MouseButton::Left, _, y) => {
+                let area = match self.list_area.get() {
+                    Some(area) if y >= area.y && y < area.y + area.height => area,
+                    _ => return Status::Running,
+                };
+                let position = List::offset(self.selected, area.height) + (y - area.y) as usize;
+                if position < self.visible().len() {
+                    self.selected = position;
+                    return self.choose();
+                }
+            }
+            _ => (),
+        }
 
-fn draw_menu(options: &[&str], selected: usize) -> Result<()> {
-    let mut stdout = stdout();
-
-    // Put all options on their own line
-    let menu = options
-        .iter()
-        .enumerate()
-        .map(|(index, option)| {
-            format!(
-                "{}{} {}.{} {}\r\n",
-                Fg(Cyan),
-                // Draw a symbol for the item that will be selected
-                if index == selected { ">" } else { " " },
-                // The key that can be pressed to select this option
-                index + 1,
-                Fg(Reset),
-                option
-            )
-        })
-        .collect::<String>();
-
-    write!(stdout, "{}{}{}", Save, menu, Restore,)?;
-
-    // Show the output on the screen
-    stdout.flush()?;
-
-    Ok(())
+        Status::Running
+    }
 }
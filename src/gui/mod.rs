@@ -1,3 +1,23 @@
+mod arrange;
+mod details;
+mod help;
+mod input;
 mod menu;
+mod profiles;
+mod screen;
+mod sentence;
+mod session;
+mod statistics;
+mod terminal;
+mod widgets;
 
-pub use menu::menu;
+pub use arrange::arrange;
+pub use details::browse_words;
+pub use help::{show_help, show_topics};
+pub use input::{dialog, input, message};
+pub use menu::{choose, menu, Selection};
+pub use profiles::{choose_profile, edit_settings};
+pub use sentence::render_sentence;
+pub use session::session;
+pub use statistics::show_statistics;
+pub use terminal::Terminal;
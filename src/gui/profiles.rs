@@ -0,0 +1,118 @@
+use crate::gui::{choose, dialog, input};
+use anyhow::Result;
+use tieng_viet::{
+    drill::Drill,
+    grammar::etymology::Etymology,
+    profile::{Profile, Store},
+    settings::Settings,
+    srs::{Algorithm, Schedule},
+};
+
+/// Let the user switch to another profile or create a new one.
+///
+/// Returns the chosen profile, `None` if it wasn't changed.
+pub fn choose_profile(store: &Store, current: &Profile) -> Result<Option<Profile>> {
+    let names = store.names()?;
+    let options = names
+        .iter()
+        .map(|name| {
+            if *name == current.name {
+                format!("{} (current)", name)
+            } else {
+                name.clone()
+            }
+        })
+        .chain(std::iter::once("New profile".to_string()))
+        .collect::<Vec<_>>();
+
+    let name = match choose(&format!("Current profile: {}", current.name), &options)?.index() {
+        Some(index) if index < names.len() => names[index].clone(),
+        Some(_) => match input("Name of the new profile:")? {
+            Some(name) => name.trim().to_string(),
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+
+    match store.load(&name) {
+        Ok(profile) => Ok(Some(profile)),
+        Err(err) => {
+            // Show what's wrong, like an invalid name, instead of quitting
+            dialog("Could not use the profile", &format!("{:#}", err))?;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Let the user change the settings, changing an option cycles through its values.
+pub fn edit_settings(settings: &mut Settings) -> Result<()> {
+    let origins = Etymology::all()
+        .iter()
+        .filter(|origin| is_practicable(origin, settings))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    loop {
+        let options = vec![
+            format!("Dialect: {}", settings.dialect),
+            format!(
+                "Only accept answers in the dialect: {}",
+                if settings.strict_dialect { "yes" } else { "no" }
+            ),
+            format!(
+                "Review scheduler: {}",
+                match settings.algorithm {
+                    Algorithm::Fsrs => "FSRS",
+                    Algorithm::Sm2 => "SuperMemo 2",
+                }
+            ),
+            format!(
+                "Words with origin: {}",
+                match &settings.origin {
+                    Some(origin) => origin.to_string(),
+                    None => "all".to_string(),
+                }
+            ),
+            "Back".to_string(),
+        ];
+
+        match choose("Settings:", &options)?.index() {
+            Some(0) => settings.dialect = settings.dialect.other(),
+            Some(1) => settings.strict_dialect = !settings.strict_dialect,
+            Some(2) => {
+                settings.algorithm = match settings.algorithm {
+                    Algorithm::Fsrs => Algorithm::Sm2,
+                    Algorithm::Sm2 => Algorithm::Fsrs,
+                }
+            }
+            // Cycle through the origins that can be practiced and back to all words
+            Some(3) => {
+                let next = match &settings.origin {
+                    Some(origin) => origins
+                        .iter()
+                        .position(|other| other.same_origin(origin))
+                        .map_or(0, |index| index + 1),
+                    None => 0,
+                };
+                settings.origin = origins.get(next).cloned();
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Whether any drill can make an exercise with words of the origin.
+fn is_practicable(origin: &Etymology, settings: &Settings) -> bool {
+    let settings = Settings {
+        origin: Some(origin.clone()),
+        ..settings.clone()
+    };
+    let mut rng = rand::thread_rng();
+
+    Drill::all().into_iter().any(|drill| {
+        drill
+            .exercise(&mut rng, &settings, &Schedule::default())
+            .is_ok()
+    })
+}
@@ -0,0 +1,349 @@
+use crate::gui::terminal::{self, Event};
+use anyhow::{bail, Result};
+use std::io::{stdout, Write};
+use termion::{
+    clear::All,
+    color::{Fg, LightBlack, Reset},
+    cursor::{Goto, Hide, Show},
+    event::{Key, MouseEvent},
+    style,
+};
+
+/// Used when the size of the terminal can't be read.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// A region of the terminal, starting at 1 like the terminal coordinates.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    /// The whole terminal.
+    pub fn terminal() -> Self {
+        let (width, height) = termion::terminal_size().unwrap_or(DEFAULT_SIZE);
+
+        Rect {
+            x: 1,
+            y: 1,
+            width,
+            height,
+        }
+    }
+
+    /// Split the region in rows, the rows that fill share the height that's left.
+    pub fn rows(self, lengths: &[Length]) -> Vec<Rect> {
+        let fixed = lengths
+            .iter()
+            .map(|length| match length {
+                Length::Fixed(height) => *height,
+                Length::Fill => 0,
+            })
+            .sum::<u16>();
+        let fills = lengths
+            .iter()
+            .filter(|length| **length == Length::Fill)
+            .count() as u16;
+        let fill = self.height.saturating_sub(fixed) / fills.max(1);
+
+        let mut y = self.y;
+        lengths
+            .iter()
+            .map(|length| {
+                let height = match length {
+                    Length::Fixed(height) => *height,
+                    Length::Fill => fill,
+                }
+                // Rows that don't fit anymore are empty
+                .min((self.y + self.height).saturating_sub(y));
+                let row = Rect { y, height, ..self };
+                y += height;
+
+                row
+            })
+            .collect()
+    }
+
+    /// A region in the middle, it's not bigger than this one.
+    pub fn centered(self, width: u16, height: u16) -> Rect {
+        let width = width.min(self.width);
+        let height = height.min(self.height);
+
+        Rect {
+            x: self.x + (self.width - width) / 2,
+            y: self.y + (self.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+/// The height of a row in a layout.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Length {
+    /// A number of lines.
+    Fixed(u16),
+    /// What's left.
+    Fill,
+}
+
+/// A character on the terminal with its colors.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Cell {
+    /// The character with its combining marks, empty when a wide character before it covers it.
+    symbol: String,
+    /// The escape sequences that color the character.
+    style: String,
+}
+
+/// What a screen draws, only the lines that changed are written so the terminal doesn't flicker.
+pub struct Frame {
+    lines: Vec<Vec<Cell>>,
+    area: Rect,
+    cursor: Option<(u16, u16)>,
+}
+
+impl Frame {
+    /// An empty frame the size of the region.
+    pub fn new(area: Rect) -> Self {
+        let blank = Cell {
+            symbol: " ".to_string(),
+            style: String::new(),
+        };
+
+        Frame {
+            lines: vec![vec![blank; area.width as usize]; area.height as usize],
+            area,
+            cursor: None,
+        }
+    }
+
+    /// The whole region that can be drawn on.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Write text at a position in a region, it's not wrapped but cut off at the end of the region.
+    ///
+    /// The text can be colored with escape sequences.
+    pub fn write(&mut self, region: Rect, x: u16, y: u16, text: &str) {
+        if y < region.y.max(self.area.y) || y >= (region.y + region.height).min(self.bottom()) {
+            return;
+        }
+        let line = &mut self.lines[(y - self.area.y) as usize];
+        let start = region.x.max(self.area.x);
+        let end = (region.x + region.width).min(self.area.x + self.area.width);
+
+        let mut x = x;
+        let mut style = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            // Keep the colors for the characters after it
+            if c == '\x1b' {
+                style.push(c);
+                if chars.next_if_eq(&'[').is_some() {
+                    style.push('[');
+                    for c in chars.by_ref() {
+                        style.push(c);
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+
+            // Combining marks belong to the character before them
+            if is_combining(c) {
+                if x > start && x <= end {
+                    line[(x - 1 - self.area.x) as usize].symbol.push(c);
+                }
+                continue;
+            }
+
+            let width = if is_wide(c) { 2 } else { 1 };
+            if x + width > end {
+                break;
+            }
+            if x >= start {
+                line[(x - self.area.x) as usize] = Cell {
+                    symbol: c.to_string(),
+                    style: style.clone(),
+                };
+                if width == 2 {
+                    line[(x + 1 - self.area.x) as usize] = Cell::default();
+                }
+            }
+            x += width;
+        }
+    }
+
+    /// The line below the frame.
+    fn bottom(&self) -> u16 {
+        self.area.y + self.area.height
+    }
+
+    /// Show the cursor at a position after drawing, it's hidden otherwise.
+    pub fn set_cursor(&mut self, x: u16, y: u16) {
+        self.cursor = Some((x, y));
+    }
+
+    /// Draw a widget in a region.
+    pub fn render<W>(&mut self, widget: &W, area: Rect)
+    where
+        W: Widget,
+    {
+        widget.draw(self, area);
+    }
+
+    /// Draw the title at the top and a hint about the keys at the bottom, returns what's left.
+    pub fn page(&mut self, title: &str, hint: &str) -> Rect {
+        let title_height = title.lines().count() as u16 + 1;
+        let rows = self
+            .area
+            .rows(&[Length::Fixed(title_height), Length::Fill, Length::Fixed(1)]);
+
+        for (y, line) in (rows[0].y..).zip(title.lines()) {
+            self.write(rows[0], rows[0].x, y, line);
+        }
+        if rows[2].height > 0 {
+            self.write(
+                rows[2],
+                rows[2].x,
+                rows[2].y,
+                &format!("{}{}{}", Fg(LightBlack), hint, Fg(Reset)),
+            );
+        }
+
+        rows[1]
+    }
+
+    /// Put the frame on the terminal, only the lines that are different from the previous frame.
+    ///
+    /// The whole terminal is cleared and drawn without a previous frame of the same size.
+    fn flush(&self, previous: Option<&Frame>) -> Result<()> {
+        let previous = previous.filter(|previous| previous.area == self.area);
+
+        let mut buffer = String::new();
+        if previous.is_none() {
+            buffer.push_str(All.as_ref());
+        }
+        for (index, line) in self.lines.iter().enumerate() {
+            if previous.is_some_and(|previous| previous.lines[index] == *line) {
+                continue;
+            }
+
+            buffer.push_str(&Goto(self.area.x, self.area.y + index as u16).to_string());
+            let mut style = "";
+            for cell in line {
+                if cell.style != style {
+                    buffer.push_str(&format!("{}{}", style::Reset, cell.style));
+                    style = &cell.style;
+                }
+                buffer.push_str(&cell.symbol);
+            }
+            buffer.push_str(style::Reset.as_ref());
+        }
+
+        let mut stdout = stdout();
+        write!(stdout, "{}", buffer)?;
+        match self.cursor {
+            Some((x, y)) => write!(stdout, "{}{}", Goto(x, y), Show)?,
+            None => write!(stdout, "{}", Hide)?,
+        }
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Whether the character is drawn on top of the character before it, like the Vietnamese tone
+/// marks when they're not part of the letter.
+fn is_combining(c: char) -> bool {
+    matches!(c, '\u{300}'..='\u{36F}' | '\u{200B}'..='\u{200F}' | '\u{FE00}'..='\u{FE0F}')
+}
+
+/// Whether the character takes two columns, like the Chinese characters of Sino-Vietnamese words.
+fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{303E}'
+            | '\u{3041}'..='\u{33FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{20000}'..='\u{3FFFD}'
+    )
+}
+
+/// Something that can be drawn in a region of a frame.
+pub trait Widget {
+    fn draw(&self, frame: &mut Frame, area: Rect);
+}
+
+/// Whether a screen is finished after handling a key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Status<T> {
+    Running,
+    Done(T),
+}
+
+/// A whole screen that's redrawn after every key press.
+pub trait Screen {
+    /// What the screen returns when it's finished.
+    type Output;
+
+    fn draw(&self, frame: &mut Frame);
+
+    fn handle(&mut self, key: Key) -> Status<Self::Output>;
+
+    /// Handle clicking and scrolling, it's ignored by default.
+    fn mouse(&mut self, _event: MouseEvent) -> Status<Self::Output> {
+        Status::Running
+    }
+}
+
+/// Draw the screen and let it handle keys until it's finished.
+///
+/// The screen is drawn again when the terminal is resized, Ctrl-Z suspends the program.
+pub fn run<S>(screen: &mut S) -> Result<S::Output>
+where
+    S: Screen,
+{
+    let mut previous: Option<Frame> = None;
+    loop {
+        let mut frame = Frame::new(Rect::terminal());
+        screen.draw(&mut frame);
+        frame.flush(previous.as_ref())?;
+        previous = Some(frame);
+
+        match terminal::next_event()? {
+            Event::Key(Key::Ctrl('z')) => {
+                terminal::suspend()?;
+                previous = None;
+            }
+            Event::Key(key) => {
+                if let Status::Done(output) = screen.handle(key) {
+                    return Ok(output);
+                }
+            }
+            Event::Mouse(event) => {
+                if let Status::Done(output) = screen.mouse(event) {
+                    return Ok(output);
+                }
+            }
+            // The terminal might have been changed by something else
+            Event::Redraw => previous = None,
+            Event::Closed => bail!("Stdin key capture returned prematurely"),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use termion::style::{NoUnderline, Underline};
+use tieng_viet::{grammar::word::Word, settings::Settings};
+
+/// Render the words of a sentence with their pronunciation.
+///
+/// The syllables of compounds are underlined together so the word boundaries are visible.
+pub fn render_sentence(words: &[Word], settings: &Settings) -> Result<String> {
+    Ok(words
+        .iter()
+        .map(|word| {
+            let pronunciation = word.pronunciation(settings.dialect)?;
+
+            Ok(if word.is_compound() {
+                format!("{}{}{} [{}]", Underline, word, NoUnderline, pronunciation)
+            } else {
+                format!("{} [{}]", word, pronunciation)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(" "))
+}
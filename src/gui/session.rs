@@ -0,0 +1,198 @@
+use crate::gui::{
+    arrange, choose, dialog, input, message, render_sentence,
+    screen::{self, Frame, Screen, Status},
+    show_topics,
+    widgets::{ProgressBar, Text},
+};
+use anyhow::Result;
+use termion::{
+    color::{Cyan, Fg, Green, Red, Reset, Yellow},
+    event::Key,
+};
+use tieng_viet::{
+    drill::{Drill, Exercise, Grade, Input, Response, Summary},
+    help,
+    profile::Profile,
+    settings::Settings,
+    srs::{self, Item},
+};
+
+/// Let the user pick a drill and practice it until they quit.
+pub fn session(settings: &Settings, profile: &mut Profile) -> Result<()> {
+    let drills = Drill::all();
+    let names = drills
+        .iter()
+        .map(|drill| drill.to_string())
+        .collect::<Vec<_>>();
+
+    let drill = match choose("Choose a drill:", &names)?.index() {
+        Some(index) => drills[index],
+        None => return Ok(()),
+    };
+
+    let mut rng = rand::thread_rng();
+    let mut summary = Summary::default();
+    'session: loop {
+        let exercise = match drill.exercise(&mut rng, settings, &profile.schedule) {
+            Ok(exercise) => exercise,
+            Err(err) => {
+                // Like when no words have the chosen origin, the settings can be changed
+                dialog("Could not make an exercise", &format!("{:#}", err))?;
+                break;
+            }
+        };
+
+        let title = format!("{} {}", drill, summary.answered + 1);
+        let response = match ask(&title, &exercise)? {
+            Some(response) => response,
+            // Stop the session when the question is cancelled
+            None => break,
+        };
+        let grade = exercise.grade(&response);
+        summary.record(grade == Grade::Correct);
+        let now = srs::now();
+        let items = Item::all(&exercise.words);
+        profile
+            .schedule
+            .review_all(items.clone(), grade.into(), now);
+        profile.record(drill.to_string(), items, grade, now);
+
+        let text = feedback(&exercise, &response, grade, settings)?;
+        loop {
+            match screen::run(&mut Feedback {
+                title: &title,
+                text: &text,
+            })? {
+                Next::Continue => break,
+                Next::Help => show_topics(
+                    "The grammar of the exercise:",
+                    &help::topics(&exercise.words),
+                )?,
+                Next::Stop => break 'session,
+            }
+        }
+    }
+
+    show_summary(&summary, profile)
+}
+
+/// Show the question and let the user answer it.
+fn ask(title: &str, exercise: &Exercise) -> Result<Option<Response>> {
+    let title = format!("{}\n\n{}", title, exercise.prompt);
+
+    Ok(match &exercise.input {
+        Input::Text { .. } | Input::English { .. } | Input::Vietnamese { .. } => {
+            input(&title)?.map(Response::Text)
+        }
+        Input::Choice { options, .. } => choose(&title, options)?.index().map(Response::Choice),
+        Input::Order { words, .. } => {
+            let words = words
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>();
+            arrange(
+                &title,
+                &words.iter().map(String::as_str).collect::<Vec<_>>(),
+            )?
+            .map(Response::Order)
+        }
+    })
+}
+
+/// Whether the answer was correct with an explanation of the answer.
+fn feedback(
+    exercise: &Exercise,
+    response: &Response,
+    grade: Grade,
+    settings: &Settings,
+) -> Result<String> {
+    let mut text = format!(
+        "{}\n\nYour answer: {}\n\n",
+        exercise.prompt,
+        exercise.response_text(response)
+    );
+    text.push_str(&match grade {
+        Grade::Correct => format!("{}Correct!{}\n", Fg(Green), Fg(Reset)),
+        Grade::AlmostCorrect => format!(
+            "{}Almost,{} check the tones: {}{}{}\n",
+            Fg(Yellow),
+            Fg(Reset),
+            Fg(Cyan),
+            exercise.answer(),
+            Fg(Reset)
+        ),
+        Grade::Wrong => format!(
+            "{}Wrong,{} the answer is: {}{}{}\n",
+            Fg(Red),
+            Fg(Reset),
+            Fg(Cyan),
+            exercise.answer(),
+            Fg(Reset)
+        ),
+    });
+    text.push_str(&format!("\n{}\n", exercise.explanation));
+    if !exercise.words.is_empty() {
+        text.push_str(&format!(
+            "\n{}\n",
+            render_sentence(&exercise.words, settings)?
+        ));
+    }
+    if let Some(translation) = &exercise.translation {
+        text.push_str(&format!("{}\n", translation));
+    }
+
+    Ok(text)
+}
+
+/// What to do after the feedback.
+enum Next {
+    Continue,
+    Help,
+    Stop,
+}
+
+/// The feedback on an answer.
+struct Feedback<'a> {
+    title: &'a str,
+    text: &'a str,
+}
+
+impl Screen for Feedback<'_> {
+    type Output = Next;
+
+    fn draw(&self, frame: &mut Frame) {
+        let area = frame.page(
+            self.title,
+            "Press enter to continue, h to explain the grammar or escape to stop.",
+        );
+        frame.render(&Text::new(self.text), area);
+    }
+
+    fn handle(&mut self, key: Key) -> Status<Next> {
+        match key {
+            Key::Char('\n') | Key::Char(' ') => Status::Done(Next::Continue),
+            Key::Char('h') | Key::Char('H') | Key::Char('?') => Status::Done(Next::Help),
+            Key::Esc | Key::Ctrl('c') | Key::Char('q') => Status::Done(Next::Stop),
+            _ => Status::Running,
+        }
+    }
+}
+
+/// Show how well the session went.
+fn show_summary(summary: &Summary, profile: &Profile) -> Result<()> {
+    message(
+        "Session finished",
+        &format!(
+            "Answered: {}\nCorrect: {}\nAccuracy: {} {:.0}%\nDue for review tomorrow: {}\nStreak: {} days\n",
+            summary.answered,
+            summary.correct,
+            ProgressBar::new(summary.accuracy(), 20),
+            summary.accuracy(),
+            profile.schedule.due(srs::now() + srs::DAY).len(),
+            profile.streak.today(srs::now())
+        ),
+        "Press enter to go back.",
+    )?;
+
+    Ok(())
+}
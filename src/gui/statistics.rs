@@ -0,0 +1,140 @@
+use crate::gui::{menu, message, widgets::ProgressBar};
+use anyhow::Result;
+use tieng_viet::{
+    drill::Summary,
+    profile::Profile,
+    srs,
+    stats::{self, Statistics},
+};
+
+/// How many days are shown in the trends.
+const DAYS: usize = 30;
+/// How many of the weakest words are shown.
+const WORDS: usize = 20;
+/// Width of the accuracy bars in characters.
+const BAR_WIDTH: usize = 20;
+
+/// Let the user pick a breakdown of the answers of the profile and show it.
+pub fn show_statistics(profile: &Profile) -> Result<()> {
+    let statistics = Statistics::new(&profile.history);
+
+    loop {
+        match menu(
+            &format!("Statistics of {}:", profile.name),
+            &[
+                "Overview",
+                "Word classes",
+                "Structures",
+                "Tones",
+                "Weakest words",
+                "Back",
+            ],
+        )?
+        .index()
+        {
+            Some(0) => overview(&statistics, profile)?,
+            Some(1) => table("Accuracy by word class", statistics.classes.iter())?,
+            Some(2) => table("Accuracy by structure", statistics.structures.iter())?,
+            Some(3) => table(
+                "Accuracy by tone",
+                statistics
+                    .tones
+                    .iter()
+                    .map(|(tone, summary)| (tone.name(), summary)),
+            )?,
+            Some(4) => table(
+                &format!("The {} weakest words", WORDS),
+                statistics
+                    .weakest_words()
+                    .iter()
+                    .take(WORDS)
+                    .map(|(id, summary)| (id, summary)),
+            )?,
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Show the totals and the trends over the last days.
+fn overview(statistics: &Statistics, profile: &Profile) -> Result<()> {
+    let now = srs::now();
+    let days = stats::daily(&profile.history, now, DAYS);
+    let answered = days
+        .iter()
+        .map(|day| Some(day.answered as f64))
+        .collect::<Vec<_>>();
+    let accuracy = days
+        .iter()
+        .map(|day| (day.answered > 0).then(|| day.accuracy()))
+        .collect::<Vec<_>>();
+
+    let text = format!(
+        "Answered: {}\nAccuracy: {:.0}%\nItems reviewed: {}\nDue for review: {}\nStreak: {} days, longest {} days\n\nLast {} days:\nAnswers  {}\nAccuracy {}\n",
+        statistics.total.answered,
+        statistics.total.accuracy(),
+        profile.schedule.reviews.len(),
+        profile.schedule.due(now).len(),
+        profile.streak.today(now),
+        profile.streak.longest,
+        DAYS,
+        sparkline(&answered),
+        sparkline(&accuracy)
+    );
+    message("Overview", &text, "Press enter to go back.")?;
+
+    Ok(())
+}
+
+/// Show the accuracy and number of reviews of every row as a bar chart.
+fn table<'a, I, S>(title: &str, rows: I) -> Result<()>
+where
+    I: Iterator<Item = (S, &'a Summary)>,
+    S: AsRef<str>,
+{
+    let rows = rows
+        .map(|(name, summary)| (name.as_ref().to_string(), *summary))
+        .collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let text = if rows.is_empty() {
+        "Nothing was practiced yet.".to_string()
+    } else {
+        rows.iter()
+            .map(|(name, summary)| {
+                format!(
+                    "{:width$} {} {:>3.0}% {:>4} reviews",
+                    name,
+                    ProgressBar::new(summary.accuracy(), BAR_WIDTH),
+                    summary.accuracy(),
+                    summary.answered,
+                    width = width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    message(title, &text, "Press enter to go back.")?;
+
+    Ok(())
+}
+
+/// Draw the values as a line of block characters, missing values are left empty.
+fn sparkline(values: &[Option<f64>]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max = values.iter().flatten().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| match value {
+            Some(value) if max > 0.0 => {
+                BLOCKS[((value / max) * (BLOCKS.len() - 1) as f64).round() as usize]
+            }
+            Some(_) => BLOCKS[0],
+            None => ' ',
+        })
+        .collect()
+}
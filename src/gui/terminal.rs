@@ -0,0 +1,272 @@
+use anyhow::{anyhow, bail, Result};
+use signal_hook::{
+    consts::{SIGCONT, SIGSTOP, SIGTSTP, SIGWINCH},
+    iterator::{Handle, Signals},
+    low_level,
+};
+use std::{
+    io::{stdin, stdout, Stdout, Write},
+    panic,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex, MutexGuard, Once, PoisonError,
+    },
+    thread::{self, JoinHandle},
+};
+use termion::{
+    cursor::{Hide, Show},
+    event::{self, Key, MouseEvent},
+    input::TermRead,
+    raw::{IntoRawMode, RawTerminal},
+    screen::{ToAlternateScreen, ToMainScreen},
+};
+
+/// Let the terminal report mouse clicks and scrolling, the same as termion's `MouseTerminal`.
+const ENABLE_MOUSE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+/// Stop reporting the mouse.
+const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// The raw terminal, `None` before it's set up and after it's dropped.
+static STATE: Mutex<State> = Mutex::new(State {
+    raw: None,
+    active: false,
+});
+
+/// The keys and signals, `None` when the terminal isn't taken over.
+static EVENTS: Mutex<Option<Receiver<Event>>> = Mutex::new(None);
+
+/// Where the keys from stdin go, they're read by one thread shared by all terminals.
+static INPUT: Mutex<Input> = Mutex::new(Input {
+    sender: None,
+    closed: false,
+});
+
+/// Starts the thread that reads stdin, it's only started once.
+static INPUT_THREAD: Once = Once::new();
+
+/// Restores the terminal on a panic, it's only installed once.
+static PANIC_HOOK: Once = Once::new();
+
+/// Something that happened while a screen is shown.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Event {
+    Key(Key),
+    Mouse(MouseEvent),
+    /// The screen must be drawn again, because it's resized or the program is continued.
+    Redraw,
+    /// Stdin was closed.
+    Closed,
+}
+
+/// Whether the terminal is taken over.
+struct State {
+    /// Restores the settings of the terminal from before raw mode when it's dropped.
+    raw: Option<RawTerminal<Stdout>>,
+    /// Whether raw mode and the alternate screen are used right now.
+    active: bool,
+}
+
+/// The receiver of the keys from stdin.
+struct Input {
+    /// The events of the current terminal, `None` when the keys are thrown away.
+    sender: Option<Sender<Event>>,
+    /// Whether stdin was closed, the thread is stopped then.
+    closed: bool,
+}
+
+/// The terminal in raw mode on the alternate screen.
+///
+/// The terminal is restored when this is dropped, when the program panics and when it's
+/// suspended with Ctrl-Z.
+pub struct Terminal {
+    /// Stops listening to the signals, `None` until the terminal is entered.
+    signals: Option<Handle>,
+    /// The thread that handles the signals.
+    signal_thread: Option<JoinHandle<()>>,
+}
+
+impl Terminal {
+    /// Take over the terminal, there can only be one.
+    pub fn new() -> Result<Self> {
+        if events().is_some() {
+            bail!("The terminal is already taken over");
+        }
+
+        // Restore the terminal before the message is printed, it's lost on the alternate screen
+        PANIC_HOOK.call_once(|| {
+            let default_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                restore();
+                default_hook(info);
+            }));
+        });
+
+        // Restores the terminal when entering it fails halfway
+        let mut terminal = Terminal {
+            signals: None,
+            signal_thread: None,
+        };
+        enter()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let (signals, signal_thread) = listen_signals(sender.clone())?;
+        terminal.signals = Some(signals);
+        terminal.signal_thread = Some(signal_thread);
+        listen_input(sender);
+        *events() = Some(receiver);
+
+        Ok(terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Stop handling the signals first, so the terminal isn't entered again after this
+        if let Some(signals) = self.signals.take() {
+            signals.close();
+        }
+        if let Some(signal_thread) = self.signal_thread.take() {
+            let _ = signal_thread.join();
+        }
+
+        restore();
+        lock().raw = None;
+        input().sender = None;
+        *events() = None;
+    }
+}
+
+/// Wait for the next key press, mouse event or signal.
+pub fn next_event() -> Result<Event> {
+    events()
+        .as_ref()
+        .ok_or_else(|| anyhow!("The terminal is not taken over"))?
+        .recv()
+        .map_err(|_| anyhow!("Stdin key capture returned prematurely"))
+}
+
+/// Give the terminal back and stop the program until it's continued, like Ctrl-Z normally does.
+pub fn suspend() -> Result<()> {
+    restore();
+    low_level::raise(SIGSTOP)?;
+
+    // Continued
+    enter()
+}
+
+/// Use raw mode and the alternate screen.
+fn enter() -> Result<()> {
+    let mut state = lock();
+    if state.active {
+        return Ok(());
+    }
+
+    match &state.raw {
+        Some(raw) => raw.activate_raw_mode()?,
+        None => {
+            state.raw = Some(
+                stdout()
+                    .into_raw_mode()
+                    .map_err(|err| anyhow!("Could not get raw mode in terminal: {}", err))?,
+            )
+        }
+    }
+    write!(stdout(), "{}{}{}", ToAlternateScreen, Hide, ENABLE_MOUSE)?;
+    stdout().flush()?;
+    state.active = true;
+
+    Ok(())
+}
+
+/// Go back to the normal screen and terminal settings.
+///
+/// Nothing panics while the state is locked, so this doesn't wait forever in the panic hook.
+fn restore() {
+    let mut state = lock();
+    if !state.active {
+        return;
+    }
+
+    // Errors can't be handled anymore, the terminal is restored as well as possible
+    let _ = write!(stdout(), "{}{}{}", DISABLE_MOUSE, ToMainScreen, Show);
+    let _ = stdout().flush();
+    if let Some(raw) = &state.raw {
+        let _ = raw.suspend_raw_mode();
+    }
+    state.active = false;
+}
+
+/// The state of the terminal, it's still usable after a panic.
+fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The receiver of the events, it's still usable after a panic.
+fn events() -> MutexGuard<'static, Option<Receiver<Event>>> {
+    EVENTS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Where the keys from stdin go, it's still usable after a panic.
+fn input() -> MutexGuard<'static, Input> {
+    INPUT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Send all keys and mouse events from stdin to the terminal.
+///
+/// A thread can't stop while it's waiting for stdin, so one thread is started for all
+/// terminals and the keys are sent to the last one.
+fn listen_input(sender: Sender<Event>) {
+    {
+        let mut input = input();
+        if input.closed {
+            let _ = sender.send(Event::Closed);
+        }
+        input.sender = Some(sender);
+    }
+
+    INPUT_THREAD.call_once(|| {
+        thread::spawn(|| {
+            for event in stdin().events() {
+                let event = match event {
+                    Ok(event::Event::Key(key)) => Event::Key(key),
+                    Ok(event::Event::Mouse(mouse)) => Event::Mouse(mouse),
+                    Ok(event::Event::Unsupported(_)) => continue,
+                    Err(_) => break,
+                };
+                if let Some(sender) = &input().sender {
+                    let _ = sender.send(event);
+                }
+            }
+
+            let mut input = input();
+            input.closed = true;
+            if let Some(sender) = &input.sender {
+                let _ = sender.send(Event::Closed);
+            }
+        });
+    });
+}
+
+/// Redraw when the terminal is resized and handle being suspended and continued.
+///
+/// The thread stops when the handle is closed.
+fn listen_signals(sender: Sender<Event>) -> Result<(Handle, JoinHandle<()>)> {
+    let mut signals = Signals::new([SIGWINCH, SIGTSTP, SIGCONT])?;
+    let handle = signals.handle();
+
+    let thread = thread::spawn(move || {
+        for signal in signals.forever() {
+            let result = match signal {
+                SIGTSTP => suspend(),
+                // Stopped by something else, the shell might have changed the terminal
+                SIGCONT => enter(),
+                _ => Ok(()),
+            };
+            if result.is_err() || sender.send(Event::Redraw).is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok((handle, thread))
+}
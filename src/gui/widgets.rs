@@ -0,0 +1,249 @@
+use crate::gui::screen::{Frame, Rect, Widget};
+use termion::color::{Cyan, Fg, Green, Red, Reset, Yellow};
+
+/// Lines of text, the lines that don't fit are left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    lines: Vec<String>,
+}
+
+impl Text {
+    pub fn new(text: &str) -> Self {
+        Text {
+            lines: text
+                .lines()
+                .map(|line| line.trim_end_matches('\r').to_string())
+                .collect(),
+        }
+    }
+
+    /// How many lines are needed to draw the text.
+    pub fn height(&self) -> u16 {
+        self.lines.len() as u16
+    }
+
+    /// The text without the first lines.
+    pub fn scrolled(&self, offset: u16) -> Text {
+        Text {
+            lines: self.lines.iter().skip(offset as usize).cloned().collect(),
+        }
+    }
+}
+
+impl Widget for Text {
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        for (y, line) in (area.y..area.y + area.height).zip(&self.lines) {
+            frame.write(area, area.x, y, line);
+        }
+
+        // Show that there's more text below
+        if self.height() > area.height && area.height > 0 {
+            frame.write(
+                area,
+                area.x + area.width.saturating_sub(1),
+                area.y + area.height - 1,
+                &format!("{}↓{}", Fg(Cyan), Fg(Reset)),
+            );
+        }
+    }
+}
+
+/// Options to pick from, the selected option is marked.
+///
+/// Every option is numbered, when the options don't fit they're scrolled to the selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct List<'a> {
+    items: &'a [String],
+    numbers: Vec<usize>,
+    selected: usize,
+}
+
+impl<'a> List<'a> {
+    pub fn new(items: &'a [String], selected: usize) -> Self {
+        List {
+            items,
+            numbers: (1..=items.len()).collect(),
+            selected,
+        }
+    }
+
+    /// Use other numbers than the position in the list.
+    pub fn numbered(self, numbers: Vec<usize>) -> Self {
+        List { numbers, ..self }
+    }
+
+    /// The first item that's drawn in a region with the height.
+    pub fn offset(selected: usize, height: u16) -> usize {
+        (selected + 1).saturating_sub(height as usize)
+    }
+}
+
+impl Widget for List<'_> {
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let offset = List::offset(self.selected, area.height);
+
+        for (y, (index, item)) in
+            (area.y..area.y + area.height).zip(self.items.iter().enumerate().skip(offset))
+        {
+            frame.write(
+                area,
+                area.x,
+                y,
+                &format!(
+                    "{}{} {}.{} {}",
+                    Fg(Cyan),
+                    // Draw a symbol for the item that will be selected
+                    if index == self.selected { ">" } else { " " },
+                    // The key that can be pressed to select this option
+                    self.numbers[index],
+                    Fg(Reset),
+                    item
+                ),
+            );
+        }
+
+        // Show that the list can be scrolled
+        let right = area.x + area.width.saturating_sub(1);
+        if offset > 0 {
+            frame.write(area, right, area.y, &format!("{}↑{}", Fg(Cyan), Fg(Reset)));
+        }
+        if offset + (area.height as usize) < self.items.len() && area.height > 0 {
+            frame.write(
+                area,
+                right,
+                area.y + area.height - 1,
+                &format!("{}↓{}", Fg(Cyan), Fg(Reset)),
+            );
+        }
+    }
+}
+
+/// A line of text that's being typed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextInput<'a> {
+    text: &'a str,
+}
+
+impl<'a> TextInput<'a> {
+    pub fn new(text: &'a str) -> Self {
+        TextInput { text }
+    }
+}
+
+impl Widget for TextInput<'_> {
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        if area.height == 0 {
+            return;
+        }
+
+        frame.write(
+            area,
+            area.x,
+            area.y,
+            &format!("{}>{} {}", Fg(Cyan), Fg(Reset), self.text),
+        );
+        frame.set_cursor(area.x + 2 + self.text.chars().count() as u16, area.y);
+    }
+}
+
+/// A percentage drawn as a bar, colored by how good it is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressBar {
+    percentage: f64,
+    width: usize,
+}
+
+impl ProgressBar {
+    pub fn new(percentage: f64, width: usize) -> Self {
+        ProgressBar { percentage, width }
+    }
+}
+
+impl std::fmt::Display for ProgressBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let filled =
+            ((self.percentage / 100.0 * self.width as f64).round() as usize).min(self.width);
+        if self.percentage >= 80.0 {
+            write!(f, "{}", Fg(Green))?;
+        } else if self.percentage >= 50.0 {
+            write!(f, "{}", Fg(Yellow))?;
+        } else {
+            write!(f, "{}", Fg(Red))?;
+        }
+
+        write!(
+            f,
+            "{}{}{}",
+            "█".repeat(filled),
+            "░".repeat(self.width - filled),
+            Fg(Reset)
+        )
+    }
+}
+
+impl Widget for ProgressBar {
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        if area.height > 0 {
+            frame.write(area, area.x, area.y, &self.to_string());
+        }
+    }
+}
+
+/// A message in a box in the middle of the region.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dialog<'a> {
+    title: &'a str,
+    text: Text,
+}
+
+impl<'a> Dialog<'a> {
+    pub fn new(title: &'a str, text: &str) -> Self {
+        Dialog {
+            title,
+            text: Text::new(text),
+        }
+    }
+}
+
+impl Widget for Dialog<'_> {
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let width = self
+            .text
+            .lines
+            .iter()
+            .map(|line| line.chars().count())
+            .chain(std::iter::once(self.title.chars().count()))
+            .max()
+            .unwrap_or(0) as u16
+            + 4;
+        let area = area.centered(width, self.text.height() + 4);
+        if area.width < 4 || area.height < 2 {
+            return;
+        }
+        let inner = area.width as usize - 2;
+
+        frame.write(
+            area,
+            area.x,
+            area.y,
+            &format!("┌{:─<inner$}┐", format!(" {} ", self.title), inner = inner),
+        );
+        for y in area.y + 1..area.y + area.height - 1 {
+            frame.write(area, area.x, y, &format!("│{:inner$}│", "", inner = inner));
+        }
+        frame.write(
+            area,
+            area.x,
+            area.y + area.height - 1,
+            &format!("└{}┘", "─".repeat(inner)),
+        );
+
+        let text_area = Rect {
+            x: area.x + 2,
+            y: area.y + 2,
+            width: area.width.saturating_sub(4),
+            height: area.height.saturating_sub(4),
+        };
+        frame.render(&self.text, text_area);
+    }
+}
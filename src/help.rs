@@ -0,0 +1,262 @@
+//! The grammar reference, generated from the documentation and the structure files.
+
+use crate::grammar::{
+    defaults_parser, dialect::Dialect, gloss, sentence::Sentence, word::Word, Generate,
+};
+use std::{collections::HashSet, sync::OnceLock};
+
+/// The source of the word classes, the documentation is used as the reference.
+const WORD_SOURCE: &str = include_str!("grammar/word.rs");
+
+/// The files with the allowed structures, the comments on top describe them.
+const STRUCTURE_FILES: [(&str, &str); 3] = [
+    ("sentences.txt", include_str!("sentences.txt")),
+    ("noun_phrases.txt", include_str!("noun_phrases.txt")),
+    ("verb_phrases.txt", include_str!("verb_phrases.txt")),
+];
+
+/// How many words of a class are shown as examples.
+const EXAMPLES: usize = 5;
+
+/// How many sentences are generated to find an example of a structure.
+const ATTEMPTS: usize = 200;
+
+/// The structure pages used to explain exercises, generated on the first use.
+static STRUCTURES: OnceLock<Vec<Topic>> = OnceLock::new();
+
+/// A page of the grammar reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Topic {
+    /// The word class or structure, the same as it's shown in the statistics.
+    pub title: String,
+    /// The explanation, words in the examples are marked with `**`.
+    pub text: String,
+}
+
+/// A page for every word class, taken from the documentation of `Class`.
+pub fn classes() -> Vec<Topic> {
+    class_docs()
+        .into_iter()
+        .filter_map(|(variant, text)| {
+            let words = Word::defaults()
+                .filter(|word| is_variant(word, &variant))
+                .collect::<Vec<_>>();
+            // Classes without words aren't used in any drill
+            let title = words.first()?.class().to_string();
+
+            let examples = words
+                .iter()
+                .take(EXAMPLES)
+                .map(|word| format!("- **{}**: {}", word, word.meaning()))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            Some(Topic {
+                title,
+                text: format!("{}\n\nExamples:\n{}", text, examples),
+            })
+        })
+        .collect()
+}
+
+/// A page for every allowed structure, with a generated example.
+pub fn structures() -> Vec<Topic> {
+    let mut rng = rand::thread_rng();
+    let sentences = (0..ATTEMPTS)
+        .filter_map(|_| {
+            Sentence::generate_in_dialect(&mut rng, vec![], Dialect::default())
+                .ok()
+                .map(|words| words.collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+
+    STRUCTURE_FILES
+        .iter()
+        .flat_map(|(file, source)| {
+            let description = source
+                .lines()
+                .filter_map(|line| line.strip_prefix('#'))
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let sentences = &sentences;
+            defaults_parser::parse_str(source).map(move |structure| {
+                let title = format!("{} ({})", structure.trim(), file);
+                let example = sentences
+                    .iter()
+                    .find(|words| {
+                        Sentence::parse(words, vec![]).is_some_and(|derivation| {
+                            derivation
+                                .structures()
+                                .iter()
+                                .any(|structure| structure.to_string() == title)
+                        })
+                    })
+                    .map(|words| {
+                        format!(
+                            "\n\nExample:\n**{}**: {}",
+                            words
+                                .iter()
+                                .map(|word| word.to_string())
+                                .collect::<Vec<_>>()
+                                .join(" "),
+                            gloss::gloss(words)
+                        )
+                    })
+                    .unwrap_or_default();
+
+                Topic {
+                    title,
+                    text: format!("{}{}", description, example),
+                }
+            })
+        })
+        .collect()
+}
+
+/// The pages that explain the grammar used by the words, to explain an exercise.
+pub fn topics(words: &[Word]) -> Vec<Topic> {
+    let mut titles = words
+        .iter()
+        .map(|word| word.class().to_string())
+        .collect::<HashSet<_>>();
+    if let Some(derivation) = Sentence::parse(words, vec![]) {
+        titles.extend(
+            derivation
+                .structures()
+                .iter()
+                .map(|structure| structure.to_string()),
+        );
+    }
+
+    classes()
+        .into_iter()
+        .chain(STRUCTURES.get_or_init(structures).iter().cloned())
+        .filter(|topic| titles.contains(&topic.title))
+        .collect()
+}
+
+/// Whether the class of the word is the variant of `Class` with the name.
+fn is_variant(word: &Word, variant: &str) -> bool {
+    let class = format!("{:?}", word.class());
+
+    class == variant || class.starts_with(&format!("{}(", variant))
+}
+
+/// The name and documentation of every variant of `Class`.
+///
+/// The documentation of the type in the variant is used when it has one, otherwise the
+/// documentation of the variant itself.
+fn class_docs() -> Vec<(String, String)> {
+    let mut docs = vec![];
+    let mut variant_doc = vec![];
+    for line in WORD_SOURCE
+        .lines()
+        .skip_while(|line| !line.starts_with("pub enum Class "))
+        .skip(1)
+        .take_while(|line| *line != "}")
+        .map(str::trim)
+    {
+        if let Some(doc) = line.strip_prefix("///") {
+            variant_doc.push(doc.trim());
+            continue;
+        }
+
+        let variant = line.trim_end_matches(',');
+        let (name, r#type) = match variant.split_once('(') {
+            Some((name, r#type)) => (name, Some(r#type.trim_end_matches(')'))),
+            None => (variant, None),
+        };
+        let text = r#type
+            .and_then(type_doc)
+            .unwrap_or_else(|| variant_doc.join("\n"));
+        variant_doc.clear();
+
+        if !text.is_empty() {
+            docs.push((name.to_string(), text));
+        }
+    }
+
+    docs
+}
+
+/// The documentation above a struct or enum in the source of the word classes.
+fn type_doc(name: &str) -> Option<String> {
+    let lines = WORD_SOURCE.lines().collect::<Vec<_>>();
+    let index = lines.iter().position(|line| {
+        *line == format!("pub struct {} {{", name)
+            || *line == format!("pub struct {} {{}}", name)
+            || *line == format!("pub enum {} {{", name)
+    })?;
+
+    let mut doc = lines[..index]
+        .iter()
+        .rev()
+        // Skip the derives
+        .skip_while(|line| line.starts_with("#["))
+        .map_while(|line| line.strip_prefix("///"))
+        // Links are written as <https://...> for rustdoc
+        .map(|line| line.trim().replace(['<', '>'], ""))
+        .collect::<Vec<_>>();
+    doc.reverse();
+
+    Some(doc.join("\n")).filter(|doc| !doc.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_classes() {
+        let classes = classes();
+        let verb = classes.iter().find(|topic| topic.title == "verb").unwrap();
+        assert!(verb
+            .text
+            .starts_with("Action, occurance or state of being."));
+        assert!(verb.text.contains("**đọc**: read"));
+        assert!(verb
+            .text
+            .contains("See: https://yourvietnamese.com/learn-vietnamese/vietnamese-verbs/"));
+
+        let interjection = classes
+            .iter()
+            .find(|topic| topic.title == "interjection")
+            .unwrap();
+        assert!(interjection.text.contains("**vâng**: yes"));
+
+        // Every class used in the words has a page
+        for word in Word::defaults() {
+            let class = word.class().to_string();
+            assert!(classes.iter().any(|topic| topic.title == class));
+        }
+    }
+
+    #[test]
+    fn test_structures() {
+        let structures = structures();
+        let sentence = structures
+            .iter()
+            .find(|topic| topic.title == "SUBJECT + VP (sentences.txt)")
+            .unwrap();
+        assert!(sentence.text.contains("Example:"));
+        assert!(structures
+            .iter()
+            .any(|topic| topic.title == "HEAD + DEMONSTRATIVE (noun_phrases.txt)"));
+    }
+
+    #[test]
+    fn test_topics() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let words = Sentence::generate(&mut rng, vec![])?.collect::<Vec<_>>();
+        let topics = topics(&words);
+        assert!(topics
+            .iter()
+            .any(|topic| topic.title == "SUBJECT + VP (sentences.txt)"));
+        assert!(topics.iter().any(|topic| topic.title == "verb"));
+
+        Ok(())
+    }
+}
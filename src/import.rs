@@ -0,0 +1,718 @@
+//! Import words from word lists and Anki decks into the lexicon.
+use crate::grammar::{
+    defaults_parser, lexicon,
+    lexicon::FILES,
+    syllable,
+    word::{Class, Verb, Word},
+};
+use anyhow::{anyhow, bail, Result};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Characters with a meaning in the word lines, they can't be part of a word.
+const RESERVED: &[char] = &[':', '(', ')', '+', '=', '#'];
+
+/// The rows of a word list or the notes of a deck.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    /// The names of the columns, empty when they're unknown.
+    pub header: Vec<String>,
+    /// The fields of the rows, in the order of the file.
+    pub rows: Vec<Row>,
+}
+
+/// A row of a word list or a note of a deck.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Row {
+    /// Where the row is in the file, starting at 1.
+    pub number: usize,
+    /// The values of the columns.
+    pub fields: Vec<String>,
+    /// Anki tags, the class of a word can be one of them.
+    pub tags: Vec<String>,
+}
+
+/// Which columns contain the word, its meaning and its class, starting at 0.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Columns {
+    /// The column with the Vietnamese word.
+    pub word: usize,
+    /// The column with the English meaning.
+    pub meaning: usize,
+    /// The column with the class, the class is inferred without it.
+    pub class: Option<usize>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Columns {
+            word: 0,
+            meaning: 1,
+            class: None,
+        }
+    }
+}
+
+/// A word that can be added to the lexicon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The row it's imported from.
+    pub row: usize,
+    /// The Vietnamese word.
+    pub word: String,
+    /// The main English meaning.
+    pub meaning: String,
+    /// `None` when it could not be inferred, it must be known before the word is written.
+    ///
+    /// Nouns are no subject or object until they're given [`NounRoles`].
+    pub class: Option<Class>,
+}
+
+impl Entry {
+    /// Let a noun be the subject or object of generated sentences, other words are left alone.
+    pub fn set_noun_roles(&mut self, roles: NounRoles) {
+        match &mut self.class {
+            Some(Class::ProperNoun(noun)) => {
+                noun.is_subject = roles.subject;
+                noun.is_object = roles.object;
+            }
+            Some(Class::CommonNoun(noun)) => {
+                noun.is_subject = roles.subject;
+                noun.is_object = roles.object;
+            }
+            _ => (),
+        }
+    }
+
+    /// The entry as a line of a word list, "word(metadata): meaning".
+    ///
+    /// The metadata are the roles, kind and classifier of a noun.
+    pub fn line(&self) -> Result<String> {
+        let (kind, is_subject, is_object, classifier) = match &self.class {
+            Some(Class::ProperNoun(noun)) => {
+                (None, noun.is_subject, noun.is_object, &noun.classifier)
+            }
+            Some(Class::CommonNoun(noun)) => (
+                Some(noun.kind.key()),
+                noun.is_subject,
+                noun.is_object,
+                &noun.classifier,
+            ),
+            _ => (None, false, false, &None),
+        };
+
+        let mut metadata = vec![];
+        metadata.extend(kind.map(|kind| (kind, None)));
+        if is_subject {
+            metadata.push(("SUBJECT", None));
+        }
+        if is_object {
+            metadata.push(("OBJECT", None));
+        }
+        metadata.extend(
+            classifier
+                .as_deref()
+                .map(|classifier| ("CLASSIFIER", Some(classifier))),
+        );
+
+        defaults_parser::write_word_line(&self.word, &metadata, Some(&self.meaning))
+    }
+}
+
+/// Whether imported nouns can be the subject or the object of generated sentences.
+///
+/// Parsed from the metadata keys like "SUBJECT+OBJECT", or "none".
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct NounRoles {
+    /// Whether the nouns can be the subject.
+    pub subject: bool,
+    /// Whether the nouns can be the object.
+    pub object: bool,
+}
+
+impl Default for NounRoles {
+    fn default() -> Self {
+        NounRoles {
+            subject: true,
+            object: true,
+        }
+    }
+}
+
+impl FromStr for NounRoles {
+    type Err = anyhow::Error;
+
+    fn from_str(roles: &str) -> Result<Self> {
+        let mut parsed = NounRoles {
+            subject: false,
+            object: false,
+        };
+        if roles.trim().eq_ignore_ascii_case("none") {
+            return Ok(parsed);
+        }
+
+        for role in roles.split('+') {
+            match role.trim().to_uppercase().as_str() {
+                "SUBJECT" => parsed.subject = true,
+                "OBJECT" => parsed.object = true,
+                _ => bail!(
+                    "\"{}\" is not a noun role, use SUBJECT, OBJECT or none",
+                    role
+                ),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// A row that could not be imported.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Skipped {
+    /// Where the row is in the file, starting at 1.
+    pub row: usize,
+    /// Why it was skipped.
+    pub reason: String,
+}
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row {}: {}", self.row, self.reason)
+    }
+}
+
+/// Find a column by its number starting at 1, or by its name in the header.
+pub fn column(name: &str, header: &[String]) -> Result<usize> {
+    if let Ok(number) = name.parse::<usize>() {
+        return number
+            .checked_sub(1)
+            .ok_or_else(|| anyhow!("Columns are numbered from 1"));
+    }
+
+    header
+        .iter()
+        .position(|column| column.trim().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            if header.is_empty() {
+                anyhow!("Column \"{}\" not found, there's no header", name)
+            } else {
+                anyhow!(
+                    "Column \"{}\" not found, the columns are: {}",
+                    name,
+                    header.join(", ")
+                )
+            }
+        })
+}
+
+/// Read a CSV or TSV word list.
+pub fn read_csv<R>(reader: R, delimiter: u8, has_header: bool) -> Result<Table>
+where
+    R: Read,
+{
+    let mut csv = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .flexible(true)
+        .from_reader(reader);
+
+    let header = if has_header {
+        csv.headers()?.iter().map(str::to_string).collect()
+    } else {
+        vec![]
+    };
+    let rows = csv
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let record = record?;
+            Ok(Row {
+                number: record
+                    .position()
+                    .map(|position| position.line() as usize)
+                    .unwrap_or(index + 1),
+                fields: record.iter().map(str::to_string).collect(),
+                tags: vec![],
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Table { header, rows })
+}
+
+/// Read the notes of an Anki deck, the header are the fields of the first note.
+///
+/// The fields are converted from HTML to text.
+#[cfg(feature = "anki")]
+pub fn read_apkg(path: &Path) -> Result<Table> {
+    use anyhow::Context;
+    use rusqlite::{Connection, OpenFlags};
+    use std::{env, io};
+
+    let mut archive = zip::ZipArchive::new(fs::File::open(path)?)
+        .with_context(|| format!("{:?} is not an Anki deck", path))?;
+    // Newer versions only have a placeholder in the old format when they also have the new one
+    let name = ["collection.anki21", "collection.anki2"]
+        .iter()
+        .find(|name| archive.index_for_name(name).is_some())
+        .filter(|_| archive.index_for_name("collection.anki21b").is_none())
+        .ok_or_else(|| {
+            anyhow!(
+                "{:?} has no collection that can be read, export it with \"Support older Anki versions\"",
+                path
+            )
+        })?;
+
+    // SQLite can only open files
+    let database = env::temp_dir().join(format!("tieng-viet-import-{}.anki2", std::process::id()));
+    io::copy(
+        &mut archive.by_name(name)?,
+        &mut fs::File::create(&database)?,
+    )?;
+    let read = || -> Result<Table> {
+        let connection = Connection::open_with_flags(&database, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let mut statement = connection.prepare("SELECT mid, flds, tags FROM notes ORDER BY id")?;
+        let notes = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let header = match notes.first() {
+            Some((model, _, _)) => field_names(&connection, *model)?,
+            None => vec![],
+        };
+        let rows = notes
+            .into_iter()
+            .enumerate()
+            .map(|(index, (_, fields, tags))| Row {
+                number: index + 1,
+                fields: fields.split('\x1f').map(html_to_text).collect(),
+                tags: tags.split_whitespace().map(str::to_string).collect(),
+            })
+            .collect();
+
+        Ok(Table { header, rows })
+    };
+    let table = read();
+    fs::remove_file(&database)?;
+
+    table
+}
+
+/// The names of the fields of a note type.
+///
+/// Newer collections have a table with them, older ones store them as JSON.
+#[cfg(feature = "anki")]
+fn field_names(connection: &rusqlite::Connection, model: i64) -> Result<Vec<String>> {
+    let names = connection
+        .prepare("SELECT name FROM fields WHERE ntid = ?1 ORDER BY ord")
+        .and_then(|mut statement| {
+            statement
+                .query_map([model], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()
+        })
+        .unwrap_or_default();
+    if !names.is_empty() {
+        return Ok(names);
+    }
+
+    let models: String = connection.query_row("SELECT models FROM col", [], |row| row.get(0))?;
+    let models: serde_json::Value = serde_json::from_str(&models)?;
+
+    Ok(models[model.to_string()]["flds"]
+        .as_array()
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|field| field["name"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Turn the rows into words, with the reasons why the other rows could not be imported.
+///
+/// The class is taken from the class column or the tags, otherwise "to swim" is a verb and a
+/// capitalized word is a proper noun. Words that are already in the lexicon are skipped.
+pub fn entries(table: &Table, columns: Columns) -> (Vec<Entry>, Vec<Skipped>) {
+    let mut entries = vec![];
+    let mut skipped = vec![];
+    // The rows the words are imported from, to skip duplicates
+    let mut imported = HashMap::new();
+
+    for row in &table.rows {
+        let entry = entry(row, columns).and_then(|entry| {
+            if let Some(existing) = Word::defaults().find(|word| {
+                word.to_string() == entry.word
+                    && entry
+                        .class
+                        .as_ref()
+                        .is_none_or(|class| class == word.class())
+            }) {
+                bail!(
+                    "\"{}\" is already in the lexicon as a {}",
+                    entry.word,
+                    existing.class()
+                );
+            }
+            if let Some(first) = imported.get(&entry.word) {
+                bail!("\"{}\" is already imported from row {}", entry.word, first);
+            }
+            imported.insert(entry.word.clone(), row.number);
+
+            Ok(entry)
+        });
+
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(err) => skipped.push(Skipped {
+                row: row.number,
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    (entries, skipped)
+}
+
+/// Turn a row into a word, fails when it can't be written in a word list.
+fn entry(row: &Row, columns: Columns) -> Result<Entry> {
+    let field = |column: usize, name: &str| -> Result<String> {
+        let field = row
+            .fields
+            .get(column)
+            .ok_or_else(|| anyhow!("there's no column {} with the {}", column + 1, name))?;
+        // Fields can span lines, word lists can't
+        let field = field.split_whitespace().collect::<Vec<_>>().join(" ");
+        if field.is_empty() {
+            bail!("the {} is empty", name);
+        }
+
+        Ok(field)
+    };
+
+    let word = field(columns.word, "word")?;
+    if let Some(c) = word.chars().find(|c| RESERVED.contains(c)) {
+        bail!("the word \"{}\" can't contain '{}'", word, c);
+    }
+    syllable::check(&word).map_err(|_| anyhow!("\"{}\" is not a Vietnamese word", word))?;
+    let mut meaning = field(columns.meaning, "meaning")?;
+    if meaning.contains('#') {
+        bail!("the meaning \"{}\" can't contain '#'", meaning);
+    }
+
+    let class = match columns.class.and_then(|column| row.fields.get(column)) {
+        Some(class) if !class.trim().is_empty() => Some(class.parse::<Class>()?),
+        _ => row.tags.iter().find_map(|tag| tag.parse::<Class>().ok()),
+    };
+    let class = match class {
+        Some(class) => Some(class),
+        None => match meaning.strip_prefix("to ") {
+            // The meanings of verbs are written without "to"
+            Some(verb) => {
+                meaning = verb.to_string();
+                Some(Class::Verb(Verb::default()))
+            }
+            None if word.starts_with(char::is_uppercase) => Some("proper noun".parse()?),
+            None => None,
+        },
+    };
+    if let Some(class) = &class {
+        if lexicon::file_name(class).is_none() {
+            bail!("{}s can't be added to the lexicon", class);
+        }
+    }
+
+    Ok(Entry {
+        row: row.number,
+        word,
+        meaning,
+        class,
+    })
+}
+
+/// Write the entries as word lists, a list for every class after a comment with its name.
+pub fn write_lines<W>(mut writer: W, entries: &[Entry]) -> Result<()>
+where
+    W: Write,
+{
+    for (name, lines) in group(entries)? {
+        writeln!(writer, "# {}", name)?;
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the entries to the word lists in a directory, see [`lexicon::read_dir`].
+///
+/// Returns the files that are written to.
+pub fn write_dir(dir: &Path, entries: &[Entry], source: &str) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    group(entries)?
+        .into_iter()
+        .map(|(name, lines)| {
+            let path = dir.join(name);
+            let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+            writeln!(file, "# Imported from {}", source)?;
+            for line in lines {
+                writeln!(file, "{}", line)?;
+            }
+
+            Ok(path)
+        })
+        .collect()
+}
+
+/// The lines of the entries by the word list they belong in, in the order of the lists.
+fn group(entries: &[Entry]) -> Result<Vec<(&'static str, Vec<String>)>> {
+    let mut groups = FILES
+        .iter()
+        .map(|(name, _)| (*name, vec![]))
+        .collect::<Vec<_>>();
+    for entry in entries {
+        let name = entry
+            .class
+            .as_ref()
+            .and_then(lexicon::file_name)
+            .ok_or_else(|| anyhow!("The class of \"{}\" is not known", entry.word))?;
+        if let Some((_, lines)) = groups.iter_mut().find(|(group, _)| *group == name) {
+            lines.push(entry.line()?);
+        }
+    }
+    groups.retain(|(_, lines)| !lines.is_empty());
+
+    Ok(groups)
+}
+
+/// Anki fields are HTML, "<b>sách</b>&nbsp;[sound:sach.mp3]" becomes "sách".
+#[cfg(any(feature = "anki", test))]
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(['<', '[']) {
+        text.push_str(&rest[..start]);
+        let end = if rest[start..].starts_with('<') {
+            '>'
+        } else if rest[start..].starts_with("[sound:") {
+            ']'
+        } else {
+            text.push('[');
+            rest = &rest[start + 1..];
+            continue;
+        };
+        // Tags separate words, like "<br>"
+        text.push(' ');
+        rest = rest[start..]
+            .find(end)
+            .map(|end| &rest[start + end + 1..])
+            .unwrap_or("");
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::word::{CommonNoun, CommonNounKind};
+    use anyhow::Result;
+    use indoc::indoc;
+    use std::env;
+
+    #[test]
+    fn test_csv() -> Result<()> {
+        let data = indoc! {"
+            Vietnamese,English,Class
+            bơi,to swim,
+            Hà Nội,Hanoi,
+            con mèo,cat,noun
+            cá heo,dolphin,
+            sách,book,noun
+            swim,bơi,
+            bơi,swim,
+            nhanh,quickly,adverb
+            ,empty,
+            lá,leaf
+        "};
+        let table = read_csv(data.as_bytes(), b',', true)?;
+        let columns = Columns {
+            word: column("vietnamese", &table.header)?,
+            meaning: column("2", &table.header)?,
+            class: Some(column("Class", &table.header)?),
+        };
+        assert!(column("Meaning", &table.header).is_err());
+
+        let (entries, skipped) = entries(&table, columns);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| Ok((entry.line()?, entry.class.as_ref().map(Class::to_string))))
+                .collect::<Result<Vec<_>>>()?,
+            vec![
+                ("bơi: swim".to_string(), Some("verb".to_string())),
+                ("Hà Nội: Hanoi".to_string(), Some("proper noun".to_string())),
+                (
+                    "con mèo(ITEM): cat".to_string(),
+                    Some("common noun".to_string())
+                ),
+                ("cá heo: dolphin".to_string(), None),
+                ("lá: leaf".to_string(), None),
+            ]
+        );
+        assert_eq!(
+            skipped.iter().map(|row| row.row).collect::<Vec<_>>(),
+            vec![6, 7, 8, 9, 10]
+        );
+        assert_eq!(
+            skipped[2].to_string(),
+            "row 8: \"bơi\" is already imported from row 2"
+        );
+
+        let mut entries = entries;
+        for entry in &mut entries {
+            entry.set_noun_roles("subject + OBJECT".parse()?);
+        }
+        let mut output = vec![];
+        assert!(write_lines(&mut output, &entries).is_err());
+        write_lines(&mut output, &entries[..3])?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            indoc! {"
+                # proper_nouns.txt
+                Hà Nội(SUBJECT + OBJECT): Hanoi
+                # common_nouns.txt
+                con mèo(ITEM + SUBJECT + OBJECT): cat
+                # verbs.txt
+                bơi: swim
+            "}
+        );
+
+        // Imported nouns are used in generated sentences
+        let words = Word::parse_file("common_nouns.txt", &entries[2].line()?)?;
+        let object = Class::CommonNoun(CommonNoun {
+            is_subject: false,
+            is_object: true,
+            kind: CommonNounKind::Item,
+            classifier: None,
+        });
+        assert!(words[0].class().fits(&object));
+
+        assert_eq!(
+            "none".parse::<NounRoles>()?,
+            NounRoles {
+                subject: false,
+                object: false
+            }
+        );
+        assert!("VERB".parse::<NounRoles>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_dir() -> Result<()> {
+        let dir = env::temp_dir().join(format!("tieng-viet-import-{}", std::process::id()));
+        let table = read_csv("bơi\tto swim\nbay\tto fly\n".as_bytes(), b'\t', false)?;
+        let (entries, skipped) = entries(&table, Columns::default());
+        assert!(skipped.is_empty());
+
+        write_dir(&dir, &entries[..1], "a.tsv")?;
+        write_dir(&dir, &entries[1..], "b.tsv")?;
+        let (words, errors) = lexicon::read_dir(&dir);
+        assert!(errors.is_empty());
+        let written = fs::read_to_string(dir.join("verbs.txt"))?;
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(
+            written,
+            "# Imported from a.tsv\nbơi: swim\n# Imported from b.tsv\nbay: fly\n"
+        );
+        assert_eq!(words.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_html_to_text() -> Result<()> {
+        assert_eq!(
+            html_to_text("<b>sách</b>&nbsp;[sound:sach.mp3]"),
+            "sách".to_string()
+        );
+        assert_eq!(html_to_text("book<br>[1]&amp;note"), "book [1]&note");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "anki")]
+    #[test]
+    fn test_apkg() -> Result<()> {
+        use rusqlite::Connection;
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let dir = env::temp_dir().join(format!("tieng-viet-apkg-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+
+        // A collection in the old format, with the field names in JSON
+        let database = dir.join("collection.anki2");
+        let connection = Connection::open(&database)?;
+        connection.execute_batch(indoc! {r#"
+            CREATE TABLE col (models TEXT);
+            INSERT INTO col VALUES ('{"1": {"flds": [{"name": "Front"}, {"name": "Back"}]}}');
+            CREATE TABLE notes (id INTEGER, mid INTEGER, flds TEXT, tags TEXT);
+            INSERT INTO notes VALUES (1, 1, 'bơi' || char(31) || 'to <i>swim</i>', '');
+            INSERT INTO notes VALUES (2, 1, 'tím' || char(31) || 'purple', ' adjective colors ');
+        "#})?;
+        drop(connection);
+
+        let path = dir.join("deck.apkg");
+        let mut zip = ZipWriter::new(fs::File::create(&path)?);
+        zip.start_file("collection.anki2", SimpleFileOptions::default())?;
+        zip.write_all(&fs::read(&database)?)?;
+        zip.finish()?;
+
+        let table = read_apkg(&path);
+        fs::remove_dir_all(&dir)?;
+        let table = table?;
+
+        assert_eq!(table.header, vec!["Front", "Back"]);
+        let (entries, skipped) = entries(&table, Columns::default());
+        assert!(skipped.is_empty());
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| Ok((entry.line()?, entry.class.as_ref().map(Class::to_string))))
+                .collect::<Result<Vec<_>>>()?,
+            vec![
+                ("bơi: swim".to_string(), Some("verb".to_string())),
+                ("tím: purple".to_string(), Some("adjective".to_string())),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+//! Generate, check and practice Vietnamese sentences.
+//!
+//! The words come from the included word lists, see [`Word::defaults`], and sentences are
+//! generated from the grammatical structures with the [`Generate`] trait:
+//!
+//! ```
+//! use tieng_viet::{Generate, Sentence};
+//!
+//! let words = Sentence::generate(&mut rand::thread_rng(), vec![])?.collect::<Vec<_>>();
+//! assert!(!words.is_empty());
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The public API follows semantic versioning, the `tieng-viet` program is built on top of it.
+
+#![warn(missing_docs)]
+
+pub mod answer;
+pub mod drill;
+pub mod export;
+pub mod grammar;
+pub mod help;
+pub mod import;
+pub mod profile;
+pub mod settings;
+pub mod srs;
+pub mod stats;
+
+pub use grammar::{
+    defaults_parser,
+    record::Record,
+    sentence::Sentence,
+    word::{Class, Word},
+    Generate,
+};
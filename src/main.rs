@@ -1,57 +1,87 @@
-// For str.split_once
-#![feature(str_split_once)]
-
-mod grammar;
+mod cli;
+#[cfg(feature = "tui")]
 mod gui;
 
 use anyhow::Result;
-use grammar::{sentence::Sentence, Generate};
-use std::io::{self, Write};
-use termion::{
-    clear::All,
-    cursor::{Goto, Hide, Show},
-    raw::IntoRawMode,
-    screen::{ToAlternateScreen, ToMainScreen},
-};
-
-//fn program(stdout: &mut Stdout) -> Result<()> {
+use clap::Parser;
+use cli::Cli;
+use tieng_viet::profile::{Profile, Store};
+
 fn main() -> Result<()> {
-    // Re-open stdout with raw mode to close it again
-    let stdout = io::stdout();
-    let mut stdout = stdout
-        .lock()
-        .into_raw_mode()
-        .expect("Could not get raw mode in terminal for stdout");
-
-    // Write the initial message
-    write!(
-        stdout,
-        "{}{}{}Welcome to Tiếng Việt, an interactive Vietnamese learning tool.{}\r\n",
-        ToAlternateScreen,
-        All,
-        Goto(1, 1),
-        Hide
-    )?;
-    // Make the output appear
-    stdout.flush()?;
-
-    // Write some random sentences.
-    let mut rng = rand::thread_rng();
-    for _ in 1..10 {
-        let words = Sentence::generate(&mut rng, vec![])?;
-        words.into_iter().for_each(|word| {
-            write!(stdout, "{} ", word.to_string()).expect("Could not write to output")
-        });
-        write!(stdout, "\r\n")?;
+    let cli = Cli::parse();
+
+    match cli.command {
+        // The commands open the data directory when they need it
+        Some(command) => command.run(cli.profile.as_deref()),
+        None => {
+            let store = Store::open()?;
+            let profile = cli::load(&store, cli.profile.as_deref())?;
+            interactive(&store, profile)
+        }
     }
-    stdout.flush()?;
+}
+
+/// Take over the terminal and show the main menu.
+#[cfg(feature = "tui")]
+fn interactive(store: &Store, mut profile: Profile) -> Result<()> {
+    use gui::Selection;
 
-    // Initial selection menu
-    let _selected = gui::menu(&["Start", "Help", "Exit"])?;
+    // The terminal is reset when it's dropped
+    let _terminal = gui::Terminal::new()?;
 
-    // Reset the terminal
-    write!(stdout, "{}{}", ToMainScreen, Show)?;
-    stdout.flush()?;
+    loop {
+        // The environment variables override the settings of the profile
+        let settings = profile.settings.clone().with_env()?;
+        profile.schedule.algorithm = settings.algorithm;
+
+        // Initial selection menu
+        let title = format!(
+            "Welcome to Tiếng Việt, an interactive Vietnamese learning tool.\nProfile: {}",
+            profile.name
+        );
+        match gui::menu(
+            &title,
+            &[
+                "Start",
+                "Words",
+                "Statistics",
+                "Settings",
+                "Profiles",
+                "Help",
+                "Exit",
+            ],
+        )? {
+            Selection::Chosen(0) => {
+                // Keep the answers that were given before something went wrong
+                let result = gui::session(&settings, &mut profile);
+                store.save(&profile)?;
+                result?;
+            }
+            Selection::Chosen(1) => gui::browse_words(&settings)?,
+            Selection::Chosen(2) => gui::show_statistics(&profile)?,
+            Selection::Chosen(3) => {
+                gui::edit_settings(&mut profile.settings)?;
+                store.save(&profile)?;
+            }
+            Selection::Chosen(4) => {
+                if let Some(chosen) = gui::choose_profile(store, &profile)? {
+                    store.save(&chosen)?;
+                    store.set_current(&chosen.name)?;
+                    profile = chosen;
+                }
+            }
+            Selection::Chosen(5) => gui::show_help()?,
+            // There's nothing to go back to
+            Selection::Back => (),
+            Selection::Chosen(_) | Selection::Quit => break,
+        }
+    }
 
     Ok(())
 }
+
+/// There's only the commands without the full-screen interface.
+#[cfg(not(feature = "tui"))]
+fn interactive(_store: &Store, _profile: Profile) -> Result<()> {
+    anyhow::bail!("Built without the \"tui\" feature, run a command like \"tieng-viet quiz\"")
+}
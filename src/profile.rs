@@ -0,0 +1,371 @@
+//! The progress and settings of learners, saved as JSON.
+
+use crate::{
+    drill::Grade,
+    settings::Settings,
+    srs::{Item, Schedule, DAY},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// The version of the profile file format, increase it and add a migration when it changes.
+const VERSION: u64 = 2;
+
+/// The profile used when none is chosen.
+pub const DEFAULT_NAME: &str = "default";
+
+/// Everything that's remembered about a learner between runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Version of the file format.
+    pub version: u64,
+    /// Name of the learner, also used as the file name.
+    pub name: String,
+    /// How the learner likes to practice.
+    pub settings: Settings,
+    /// When the words and structures should be reviewed.
+    pub schedule: Schedule,
+    /// All answers given, oldest first.
+    pub history: Vec<Answer>,
+    /// Days in a row the learner practiced.
+    pub streak: Streak,
+}
+
+impl Profile {
+    /// A new profile without any progress.
+    pub fn new(name: &str) -> Result<Self> {
+        validate_name(name)?;
+        let settings = Settings::default();
+
+        Ok(Profile {
+            version: VERSION,
+            name: name.to_string(),
+            schedule: Schedule::new(settings.algorithm),
+            settings,
+            history: vec![],
+            streak: Streak::default(),
+        })
+    }
+
+    /// Remember an answer to an exercise.
+    pub fn record(&mut self, drill: String, items: Vec<Item>, grade: Grade, time: u64) {
+        self.history.push(Answer {
+            time,
+            drill,
+            items,
+            grade,
+        });
+        self.streak.practice(time);
+    }
+}
+
+/// A single answer to an exercise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Answer {
+    /// When it was answered, in seconds since the Unix epoch.
+    pub time: u64,
+    /// The name of the drill.
+    pub drill: String,
+    /// The words and structures that were practiced.
+    pub items: Vec<Item>,
+    /// How well it was answered.
+    pub grade: Grade,
+}
+
+/// Days in a row the learner practiced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Streak {
+    /// Days in a row up to the last day.
+    pub current: u32,
+    /// The most days in a row ever.
+    pub longest: u32,
+    /// The last day that was practiced, in days since the Unix epoch.
+    pub last_day: Option<u64>,
+}
+
+impl Streak {
+    /// Extend the streak if the learner practiced the day before.
+    pub fn practice(&mut self, time: u64) {
+        let day = time / DAY;
+        match self.last_day {
+            Some(last_day) if last_day == day => return,
+            Some(last_day) if last_day + 1 == day => self.current += 1,
+            _ => self.current = 1,
+        }
+        self.last_day = Some(day);
+        self.longest = self.longest.max(self.current);
+    }
+
+    /// The streak as it is today, it's broken when yesterday wasn't practiced.
+    pub fn today(&self, time: u64) -> u32 {
+        match self.last_day {
+            Some(last_day) if last_day + 1 >= time / DAY => self.current,
+            _ => 0,
+        }
+    }
+}
+
+/// The directory with all profiles and the lexicon of the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    /// Use the profiles in a directory, it's created when saving.
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Store { dir: dir.into() }
+    }
+
+    /// Use the profiles in the data directory of the user.
+    ///
+    /// This is `$TIENG_VIET_DATA_DIR` if it's set, otherwise `tieng-viet` in
+    /// `$XDG_DATA_HOME` or `~/.local/share`.
+    ///
+    /// See: <https://specifications.freedesktop.org/basedir-spec/latest/>
+    pub fn open() -> Result<Self> {
+        if let Some(dir) = env::var_os("TIENG_VIET_DATA_DIR") {
+            return Ok(Store::new(dir));
+        }
+
+        let data_dir = env::var_os("XDG_DATA_HOME")
+            .filter(|dir| Path::new(dir).is_absolute())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+            .ok_or_else(|| anyhow!("Could not find the data directory, set $HOME"))?;
+
+        Ok(Store::new(data_dir.join("tieng-viet")))
+    }
+
+    /// The names of all saved profiles, sorted.
+    pub fn names(&self) -> Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            // Nothing is saved yet
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut names = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().to_string())
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Load a profile, a new one is created when it doesn't exist.
+    pub fn load(&self, name: &str) -> Result<Profile> {
+        let path = self.path(name)?;
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Profile::new(name),
+            Err(err) => return Err(err).with_context(|| format!("Could not read {:?}", path)),
+        };
+
+        let profile = serde_json::from_str(&data)
+            .with_context(|| format!("Profile {:?} is not valid JSON", path))?;
+        serde_json::from_value(migrate(profile)?)
+            .with_context(|| format!("Profile {:?} is not a valid profile", path))
+    }
+
+    /// Save a profile, the file is replaced at once so it's never half written.
+    pub fn save(&self, profile: &Profile) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Could not create directory {:?}", self.dir))?;
+
+        let path = self.path(&profile.name)?;
+        let temporary = path.with_extension("json.tmp");
+        {
+            let mut file = fs::File::create(&temporary)?;
+            file.write_all(serde_json::to_string_pretty(profile)?.as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&temporary, &path)
+            .with_context(|| format!("Could not save profile to {:?}", path))
+    }
+
+    /// The name of the profile that was used last.
+    pub fn current(&self) -> String {
+        fs::read_to_string(self.dir.join("current"))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| validate_name(name).is_ok())
+            .unwrap_or_else(|| DEFAULT_NAME.to_string())
+    }
+
+    /// Use the profile the next time.
+    pub fn set_current(&self, name: &str) -> Result<()> {
+        validate_name(name)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join("current"), name)?;
+
+        Ok(())
+    }
+
+    /// The word lists added by the user, see [`read_dir`](crate::grammar::lexicon::read_dir).
+    pub fn lexicon_dir(&self) -> PathBuf {
+        self.dir.join("lexicon")
+    }
+
+    /// Where the profile is stored.
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name)?;
+
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+}
+
+/// Names are used as file names, so only allow letters, digits, spaces, '-' and '_'.
+fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("The name of a profile can't be empty");
+    }
+    if name.trim() != name
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_')
+    {
+        bail!(
+            "The name {:?} can only contain letters, digits, spaces, '-' and '_'",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// Upgrade a profile saved by an older version to the current file format.
+///
+/// Every version bump gets a step here that upgrades the profile by one version.
+fn migrate(mut profile: Value) -> Result<Value> {
+    loop {
+        let version = profile
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow!("Profile has no version"))?;
+
+        match version {
+            VERSION => return Ok(profile),
+            version if version > VERSION => bail!(
+                "Profile was saved by a newer version of this program (format {})",
+                version
+            ),
+            // Answers didn't remember what was practiced
+            1 => {
+                if let Some(history) = profile["history"].as_array_mut() {
+                    for answer in history {
+                        answer["items"] = Value::Array(vec![]);
+                    }
+                }
+            }
+            version => bail!("Can't upgrade a profile from format {}", version),
+        }
+
+        profile["version"] = (version + 1).into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srs::Rating;
+    use anyhow::Result;
+
+    /// A directory that's unique for the test.
+    fn store(test: &str) -> Store {
+        let dir = env::temp_dir().join(format!("tieng-viet-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Store::new(dir)
+    }
+
+    #[test]
+    fn test_save() -> Result<()> {
+        let store = store("save");
+        assert!(store.names()?.is_empty());
+
+        let mut profile = store.load("Linh")?;
+        profile
+            .schedule
+            .review(Item::Word("verb đọc".to_string()), Rating::Good, 0);
+        profile.record(
+            "Word order".to_string(),
+            vec![Item::Word("verb đọc".to_string())],
+            Grade::Correct,
+            0,
+        );
+        store.save(&profile)?;
+        store.set_current("Linh")?;
+
+        assert_eq!(store.names()?, vec!["Linh"]);
+        assert_eq!(store.current(), "Linh");
+        assert_eq!(store.load("Linh")?, profile);
+
+        fs::remove_dir_all(&store.dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_names() {
+        assert!(Profile::new("Phúc").is_ok());
+        assert!(Profile::new("").is_err());
+        assert!(Profile::new("../Phúc").is_err());
+    }
+
+    #[test]
+    fn test_migrate() -> Result<()> {
+        let profile = serde_json::to_value(Profile::new(DEFAULT_NAME)?)?;
+        assert_eq!(migrate(profile.clone())?, profile);
+
+        let mut newer = profile.clone();
+        newer["version"] = (VERSION + 1).into();
+        assert!(migrate(newer).is_err());
+
+        // The first format had no items in the answers
+        let mut old = Profile::new(DEFAULT_NAME)?;
+        old.record("Word order".to_string(), vec![], Grade::Correct, 0);
+        let mut old = serde_json::to_value(old)?;
+        old["version"] = 1.into();
+        old["history"][0].as_object_mut().unwrap().remove("items");
+        old["settings"].as_object_mut().unwrap().remove("origin");
+        let migrated: Profile = serde_json::from_value(migrate(old)?)?;
+        assert_eq!(migrated.version, VERSION);
+        assert!(migrated.history[0].items.is_empty());
+        assert_eq!(migrated.settings.origin, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut streak = Streak::default();
+        streak.practice(0);
+        streak.practice(DAY / 2);
+        assert_eq!(streak.current, 1);
+
+        streak.practice(DAY);
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.today(2 * DAY), 2);
+        assert_eq!(streak.today(3 * DAY), 0);
+
+        streak.practice(5 * DAY);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 2);
+    }
+}
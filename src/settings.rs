@@ -0,0 +1,79 @@
+//! Preferences of the learner.
+
+use crate::{
+    grammar::{dialect::Dialect, etymology::Etymology, word::Word},
+    srs::Algorithm,
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Preferences of the learner.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// The dialect used for generating words and for the pronunciation.
+    pub dialect: Dialect,
+    /// Only accept answers in the chosen dialect, otherwise variants of all dialects are accepted.
+    pub strict_dialect: bool,
+    /// How the reviews of words and structures are scheduled.
+    pub algorithm: Algorithm,
+    /// Only practice words with this origin, `None` for all words.
+    pub origin: Option<Etymology>,
+}
+
+impl Settings {
+    /// Override the settings with the environment variables that are set.
+    ///
+    /// - `TIENG_VIET_DIALECT`: `northern` or `southern`.
+    /// - `TIENG_VIET_STRICT_DIALECT`: `1` to only accept answers in the chosen dialect.
+    /// - `TIENG_VIET_SCHEDULER`: `fsrs` or `sm2`.
+    /// - `TIENG_VIET_ORIGIN`: `sino`, `native`, `french`, `english` or `all`.
+    pub fn with_env(mut self) -> Result<Self> {
+        if let Ok(dialect) = env::var("TIENG_VIET_DIALECT") {
+            self.dialect = dialect.parse()?;
+        }
+        if let Ok(strict) = env::var("TIENG_VIET_STRICT_DIALECT") {
+            self.strict_dialect = strict == "1" || strict == "true";
+        }
+        if let Ok(algorithm) = env::var("TIENG_VIET_SCHEDULER") {
+            self.algorithm = algorithm.parse()?;
+        }
+        if let Ok(origin) = env::var("TIENG_VIET_ORIGIN") {
+            self.origin = match origin.as_str() {
+                "all" => None,
+                origin => Some(origin.parse()?),
+            };
+        }
+
+        Ok(self)
+    }
+
+    /// The dialect answers are checked against, `None` if all dialects are accepted.
+    pub fn answer_dialect(&self) -> Option<Dialect> {
+        if self.strict_dialect {
+            Some(self.dialect)
+        } else {
+            None
+        }
+    }
+
+    /// All dialects of which the answers are accepted.
+    pub fn answer_dialects(&self) -> Vec<Dialect> {
+        match self.answer_dialect() {
+            Some(dialect) => vec![dialect],
+            None => Dialect::all().to_vec(),
+        }
+    }
+
+    /// Whether a word can be practiced, it must have the chosen origin.
+    pub fn allows(&self, word: &Word) -> bool {
+        self.origin
+            .as_ref()
+            .is_none_or(|origin| word.has_origin(origin))
+    }
+
+    /// Whether a sentence can be practiced, at least one of its words must have the chosen origin.
+    pub fn allows_sentence(&self, words: &[Word]) -> bool {
+        self.origin.is_none() || words.iter().any(|word| self.allows(word))
+    }
+}
@@ -0,0 +1,114 @@
+//! The Free Spaced Repetition Scheduler.
+
+use crate::srs::Rating;
+use serde::{Deserialize, Serialize};
+
+/// Default parameters of FSRS 4.5, optimized on a large set of reviews.
+const WEIGHTS: [f64; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+/// Shape of the forgetting curve.
+const DECAY: f64 = -0.5;
+/// Makes the chance of remembering 90% when the elapsed days equal the stability.
+const FACTOR: f64 = 19.0 / 81.0;
+/// The chance of remembering an item when it's reviewed.
+const DESIRED_RETENTION: f64 = 0.9;
+
+/// How well an item is remembered according to the Free Spaced Repetition Scheduler.
+///
+/// See: <https://github.com/open-spaced-repetition/fsrs4anki/wiki/The-Algorithm>
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// Days until the chance of remembering drops to 90%.
+    pub stability: f64,
+    /// How hard the item is, from 1 to 10.
+    pub difficulty: f64,
+}
+
+impl State {
+    /// The chance of remembering the item after some days.
+    pub fn retrievability(&self, elapsed: f64) -> f64 {
+        (1.0 + FACTOR * elapsed / self.stability).powf(DECAY)
+    }
+
+    /// Days until the item should be reviewed.
+    pub fn interval(&self) -> f64 {
+        (self.stability / FACTOR * (DESIRED_RETENTION.powf(1.0 / DECAY) - 1.0)).max(1.0)
+    }
+}
+
+/// The state after reviewing the item, `None` if it's the first review.
+pub fn review(state: Option<State>, rating: Rating, elapsed: f64) -> State {
+    let w = &WEIGHTS;
+    let grade = match rating {
+        Rating::Again => 1.0,
+        Rating::Hard => 2.0,
+        Rating::Good => 3.0,
+    };
+
+    let state = match state {
+        Some(state) => state,
+        None => {
+            return State {
+                stability: w[grade as usize - 1],
+                difficulty: initial_difficulty(grade),
+            }
+        }
+    };
+
+    let retrievability = state.retrievability(elapsed);
+    let difficulty = (w[7] * initial_difficulty(4.0)
+        + (1.0 - w[7]) * (state.difficulty - w[6] * (grade - 3.0)))
+        .clamp(1.0, 10.0);
+
+    let stability = match rating {
+        Rating::Again => {
+            w[11]
+                * state.difficulty.powf(-w[12])
+                * ((state.stability + 1.0).powf(w[13]) - 1.0)
+                * (w[14] * (1.0 - retrievability)).exp()
+        }
+        Rating::Hard | Rating::Good => {
+            let hard_penalty = if rating == Rating::Hard { w[15] } else { 1.0 };
+
+            state.stability
+                * (w[8].exp()
+                    * (11.0 - state.difficulty)
+                    * state.stability.powf(-w[9])
+                    * ((w[10] * (1.0 - retrievability)).exp() - 1.0)
+                    * hard_penalty
+                    + 1.0)
+        }
+    };
+
+    State {
+        stability,
+        difficulty,
+    }
+}
+
+/// The difficulty after the first review.
+fn initial_difficulty(grade: f64) -> f64 {
+    (WEIGHTS[4] - (grade - 3.0) * WEIGHTS[5]).clamp(1.0, 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review() {
+        let state = review(None, Rating::Good, 0.0);
+        assert_eq!(state.stability, WEIGHTS[2]);
+        assert!((state.retrievability(state.stability) - 0.9).abs() < 1e-9);
+
+        // Remembering an item makes it more stable, forgetting less stable
+        let remembered = review(Some(state), Rating::Good, state.interval());
+        assert!(remembered.stability > state.stability);
+        assert!(remembered.interval() > state.interval());
+        let forgotten = review(Some(state), Rating::Again, state.interval());
+        assert!(forgotten.stability < state.stability);
+        assert!(forgotten.difficulty > state.difficulty);
+    }
+}
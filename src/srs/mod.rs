@@ -0,0 +1,346 @@
+//! Spaced repetition of the practiced words and structures.
+
+pub mod fsrs;
+pub mod sm2;
+
+use crate::{
+    drill::Grade,
+    grammar::{sentence::Sentence, word::Word, Generate},
+    settings::Settings,
+};
+use anyhow::{anyhow, bail, Result};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Seconds in a day.
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// How many candidates are compared when choosing what to practice.
+const CANDIDATES: usize = 10;
+
+/// How many sentences are generated for every candidate before giving up.
+const ATTEMPTS: usize = 20;
+
+/// Something the learner can remember or forget.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Item {
+    /// A word, by it's id.
+    Word(String),
+    /// A grammatical structure, "SUBJECT + VP (sentences.txt)".
+    Structure(String),
+}
+
+impl Item {
+    /// All items practiced with the words, the words themselves and the structures they form.
+    pub fn all(words: &[Word]) -> Vec<Item> {
+        let structures = Sentence::parse(words, vec![])
+            .map(|derivation| {
+                derivation
+                    .structures()
+                    .iter()
+                    .map(|structure| Item::Structure(structure.to_string()))
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new);
+
+        words
+            .iter()
+            .map(|word| Item::Word(word.id()))
+            .chain(structures)
+            .collect()
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Item::Word(id) | Item::Structure(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// How well an item was remembered.
+///
+/// There's no rating for remembering an item easily, answers are only graded as right or wrong.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rating {
+    /// Forgotten.
+    Again,
+    /// Remembered with mistakes.
+    Hard,
+    /// Remembered.
+    Good,
+}
+
+impl From<Grade> for Rating {
+    fn from(grade: Grade) -> Self {
+        match grade {
+            Grade::Correct => Rating::Good,
+            Grade::AlmostCorrect => Rating::Hard,
+            Grade::Wrong => Rating::Again,
+        }
+    }
+}
+
+/// The algorithm that decides when items are reviewed.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Algorithm {
+    /// SuperMemo 2, the classic algorithm of Anki.
+    Sm2,
+    /// Free Spaced Repetition Scheduler, models the chance of remembering an item.
+    #[default]
+    Fsrs,
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(algorithm: &str) -> Result<Self> {
+        match algorithm.to_lowercase().as_str() {
+            "sm2" | "sm-2" => Ok(Algorithm::Sm2),
+            "fsrs" => Ok(Algorithm::Fsrs),
+            _ => bail!("Unrecognized scheduling algorithm {}", algorithm),
+        }
+    }
+}
+
+/// How well an item is remembered, depending on the algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Memory {
+    /// The state of SuperMemo 2.
+    Sm2(sm2::State),
+    /// The state of the Free Spaced Repetition Scheduler.
+    Fsrs(fsrs::State),
+}
+
+/// The review history of a single item.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    /// When the item was last reviewed, in seconds since the Unix epoch.
+    pub last: u64,
+    /// When the item should be reviewed again.
+    pub due: u64,
+    /// How many times the item was reviewed.
+    pub reviews: u32,
+    /// How many times the item was forgotten.
+    pub lapses: u32,
+    /// How well the item is remembered.
+    pub memory: Memory,
+}
+
+/// When all items the learner practiced should be reviewed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// The algorithm used for new items.
+    pub algorithm: Algorithm,
+    /// Stored as a list because the items can't be keys in JSON.
+    #[serde(with = "review_list")]
+    pub reviews: HashMap<Item, Review>,
+}
+
+impl Schedule {
+    /// An empty schedule.
+    pub fn new(algorithm: Algorithm) -> Self {
+        Schedule {
+            algorithm,
+            reviews: HashMap::new(),
+        }
+    }
+
+    /// Update the schedule of an item after it's reviewed.
+    pub fn review(&mut self, item: Item, rating: Rating, now: u64) {
+        let previous = self.reviews.get(&item);
+        let elapsed = previous
+            .map(|review| now.saturating_sub(review.last) as f64 / DAY as f64)
+            .unwrap_or_default();
+
+        // The state of another algorithm can't be used, start over
+        let memory = match (self.algorithm, previous.map(|review| review.memory)) {
+            (Algorithm::Sm2, Some(Memory::Sm2(state))) => {
+                Memory::Sm2(sm2::review(Some(state), rating))
+            }
+            (Algorithm::Sm2, _) => Memory::Sm2(sm2::review(None, rating)),
+            (Algorithm::Fsrs, Some(Memory::Fsrs(state))) => {
+                Memory::Fsrs(fsrs::review(Some(state), rating, elapsed))
+            }
+            (Algorithm::Fsrs, _) => Memory::Fsrs(fsrs::review(None, rating, elapsed)),
+        };
+        let interval = match memory {
+            Memory::Sm2(state) => state.interval,
+            Memory::Fsrs(state) => state.interval(),
+        };
+
+        let review = Review {
+            last: now,
+            due: now + (interval * DAY as f64) as u64,
+            reviews: previous.map_or(0, |review| review.reviews) + 1,
+            lapses: previous.map_or(0, |review| review.lapses) + u32::from(rating == Rating::Again),
+            memory,
+        };
+        self.reviews.insert(item, review);
+    }
+
+    /// Update the schedule of all items after they are practiced together.
+    pub fn review_all(&mut self, items: Vec<Item>, rating: Rating, now: u64) {
+        for item in items {
+            self.review(item, rating, now);
+        }
+    }
+
+    /// Whether the item was seen before and should be reviewed.
+    pub fn is_due(&self, item: &Item, now: u64) -> bool {
+        self.reviews
+            .get(item)
+            .is_some_and(|review| review.due <= now)
+    }
+
+    /// All items that should be reviewed.
+    pub fn due(&self, now: u64) -> Vec<&Item> {
+        let mut due = self
+            .reviews
+            .keys()
+            .filter(|item| self.is_due(item, now))
+            .collect::<Vec<_>>();
+        due.sort();
+
+        due
+    }
+
+    /// How much practicing the items helps, due items count more than new ones.
+    pub fn urgency(&self, items: &[Item], now: u64) -> usize {
+        items
+            .iter()
+            .map(|item| match self.reviews.get(item) {
+                Some(review) if review.due <= now => 2,
+                Some(_) => 0,
+                None => 1,
+            })
+            .sum()
+    }
+
+    /// Pick a random candidate, preferring the ones with items that are due.
+    pub fn choose<R, T, I, F>(&self, rng: &mut R, candidates: I, items: F) -> Option<T>
+    where
+        R: Rng,
+        I: Iterator<Item = T>,
+        F: Fn(&T) -> Vec<Item>,
+    {
+        let now = now();
+        let mut candidates = candidates.choose_multiple(rng, CANDIDATES);
+        // The sample is not in a random order
+        candidates.shuffle(rng);
+
+        candidates
+            .into_iter()
+            .max_by_key(|candidate| self.urgency(&items(candidate), now))
+    }
+
+    /// Generate a sentence in the dialect of the settings with a word of the chosen origin,
+    /// preferring sentences with words and structures that are due.
+    pub fn sentence<R>(&self, rng: &mut R, settings: &Settings) -> Result<Vec<Word>>
+    where
+        R: Rng,
+    {
+        let mut candidates = vec![];
+        for _ in 0..CANDIDATES * ATTEMPTS {
+            if candidates.len() == CANDIDATES {
+                break;
+            }
+
+            let words =
+                Sentence::generate_in_dialect(rng, vec![], settings.dialect)?.collect::<Vec<_>>();
+            if settings.allows_sentence(&words) {
+                candidates.push(words);
+            }
+        }
+
+        self.choose(rng, candidates.into_iter(), |words| Item::all(words))
+            .ok_or_else(|| anyhow!("Could not generate a sentence with words of the chosen origin"))
+    }
+}
+
+/// Serialize the reviews as a list of items with their review.
+mod review_list {
+    use super::{Item, Review};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S>(reviews: &HashMap<Item, Review>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut list = reviews.iter().collect::<Vec<_>>();
+        // Keep the order the same between saves
+        list.sort_by_key(|&(item, _)| item);
+
+        list.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<Item, Review>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(Item, Review)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// The current time in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule() {
+        for algorithm in &[Algorithm::Sm2, Algorithm::Fsrs] {
+            let mut schedule = Schedule::new(*algorithm);
+            let item = Item::Word("verb đọc".to_string());
+            let now = 1_000_000;
+
+            // New items are not due
+            assert!(!schedule.is_due(&item, now));
+            assert_eq!(schedule.urgency(std::slice::from_ref(&item), now), 1);
+
+            schedule.review(item.clone(), Rating::Good, now);
+            assert!(!schedule.is_due(&item, now));
+            assert!(schedule.due(now + 30 * DAY).contains(&&item));
+
+            schedule.review(item.clone(), Rating::Again, now + 30 * DAY);
+            let review = schedule.reviews[&item];
+            assert_eq!(review.reviews, 2);
+            assert_eq!(review.lapses, 1);
+        }
+    }
+
+    #[test]
+    fn test_items() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let words = Sentence::generate(&mut rng, vec![])?.collect::<Vec<_>>();
+
+        // All words and at least the sentence structure
+        let items = Item::all(&words);
+        assert!(items.len() > words.len());
+        assert!(items.iter().any(
+            |item| matches!(item, Item::Structure(structure) if structure.contains("sentences.txt"))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! The SuperMemo 2 algorithm.
+
+use crate::srs::Rating;
+use serde::{Deserialize, Serialize};
+
+/// The ease a new item starts with.
+const INITIAL_EASE: f64 = 2.5;
+/// Items never get harder than this.
+const MINIMUM_EASE: f64 = 1.3;
+
+/// How well an item is remembered according to SuperMemo 2.
+///
+/// See: <https://super-memory.com/english/ol/sm2.htm>
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// How many times in a row the item was remembered.
+    pub repetitions: u32,
+    /// Days until the next review.
+    pub interval: f64,
+    /// How fast the interval grows.
+    pub ease: f64,
+}
+
+/// The state after reviewing the item, `None` if it's the first review.
+pub fn review(state: Option<State>, rating: Rating) -> State {
+    let State {
+        repetitions,
+        interval,
+        ease,
+    } = state.unwrap_or(State {
+        repetitions: 0,
+        interval: 0.0,
+        ease: INITIAL_EASE,
+    });
+
+    // The quality of the response on a scale of 0 to 5
+    let quality = match rating {
+        Rating::Again => 1.0,
+        Rating::Hard => 3.0,
+        Rating::Good => 4.0,
+    };
+    let ease = (ease + 0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02)).max(MINIMUM_EASE);
+
+    match (rating, repetitions) {
+        // Forgotten items start over
+        (Rating::Again, _) => State {
+            repetitions: 0,
+            interval: 1.0,
+            ease,
+        },
+        (_, 0) => State {
+            repetitions: 1,
+            interval: 1.0,
+            ease,
+        },
+        (_, 1) => State {
+            repetitions: 2,
+            interval: 6.0,
+            ease,
+        },
+        (_, _) => State {
+            repetitions: repetitions + 1,
+            interval: (interval * ease).round(),
+            ease,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review() {
+        let state = review(None, Rating::Good);
+        assert_eq!(state.interval, 1.0);
+        let state = review(Some(state), Rating::Good);
+        assert_eq!(state.interval, 6.0);
+        let state = review(Some(state), Rating::Good);
+        assert_eq!(state.interval, 15.0);
+
+        // Forgetting resets the interval and makes the item harder
+        let forgotten = review(Some(state), Rating::Again);
+        assert_eq!(forgotten.interval, 1.0);
+        assert!(forgotten.ease < state.ease);
+    }
+}
@@ -0,0 +1,175 @@
+//! How well words, structures and tones are known.
+
+use crate::{
+    drill::{Grade, Summary},
+    grammar::{syllable::Tone, word::Word},
+    profile::Answer,
+    srs::{Item, DAY},
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The accuracy of all answers, broken down by what was practiced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    /// Of all answers.
+    pub total: Summary,
+    /// By the class of the practiced words.
+    pub classes: BTreeMap<String, Summary>,
+    /// By grammatical structure, "SUBJECT + VP (sentences.txt)".
+    pub structures: BTreeMap<String, Summary>,
+    /// By the tones in the practiced words.
+    pub tones: BTreeMap<Tone, Summary>,
+    /// By word id.
+    pub words: BTreeMap<String, Summary>,
+}
+
+impl Statistics {
+    /// Count all answers.
+    pub fn new(history: &[Answer]) -> Self {
+        let words = Word::defaults()
+            .map(|word| (word.id(), word))
+            .collect::<HashMap<_, _>>();
+
+        let mut statistics = Statistics::default();
+        for answer in history {
+            let correct = answer.grade == Grade::Correct;
+            statistics.total.record(correct);
+
+            // Every category is only counted once per answer
+            let mut classes = BTreeSet::new();
+            let mut tones = BTreeSet::new();
+            for item in &answer.items {
+                match item {
+                    Item::Word(id) => {
+                        statistics
+                            .words
+                            .entry(id.clone())
+                            .or_default()
+                            .record(correct);
+
+                        // Words that were removed from the word lists only count by their id
+                        if let Some(word) = words.get(id) {
+                            classes.insert(word.class().to_string());
+                            tones.extend(word.syllables().into_iter().flatten().map(|s| s.tone));
+                        }
+                    }
+                    Item::Structure(structure) => statistics
+                        .structures
+                        .entry(structure.clone())
+                        .or_default()
+                        .record(correct),
+                }
+            }
+            for class in classes {
+                statistics.classes.entry(class).or_default().record(correct);
+            }
+            for tone in tones {
+                statistics.tones.entry(tone).or_default().record(correct);
+            }
+        }
+
+        statistics
+    }
+
+    /// The words with the lowest accuracy first, words answered more often go first on a tie.
+    pub fn weakest_words(&self) -> Vec<(&str, Summary)> {
+        let mut words = self
+            .words
+            .iter()
+            .map(|(id, summary)| (id.as_str(), *summary))
+            .collect::<Vec<_>>();
+        words.sort_by(|(_, a), (_, b)| {
+            a.accuracy()
+                .total_cmp(&b.accuracy())
+                .then(b.answered.cmp(&a.answered))
+        });
+
+        words
+    }
+}
+
+/// The answers of each of the last days, the last element is today.
+pub fn daily(history: &[Answer], now: u64, days: usize) -> Vec<Summary> {
+    let today = now / DAY;
+    let mut summaries = vec![Summary::default(); days];
+    for answer in history {
+        let ago = today.saturating_sub(answer.time / DAY) as usize;
+        if ago < days {
+            summaries[days - 1 - ago].record(answer.grade == Grade::Correct);
+        }
+    }
+
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    fn answer(time: u64, items: &[Item], grade: Grade) -> Answer {
+        Answer {
+            time,
+            drill: "Word order".to_string(),
+            items: items.to_vec(),
+            grade,
+        }
+    }
+
+    #[test]
+    fn test_statistics() -> Result<()> {
+        let word = Word::defaults()
+            .find(|word| word.to_string() == "đọc")
+            .unwrap();
+        let items = vec![
+            Item::Word(word.id()),
+            Item::Word(word.id()),
+            Item::Structure("VERB (verb_phrases.txt)".to_string()),
+        ];
+        let history = vec![
+            answer(0, &items, Grade::Correct),
+            answer(DAY, &items, Grade::AlmostCorrect),
+        ];
+
+        let statistics = Statistics::new(&history);
+        assert_eq!(statistics.total.answered, 2);
+        assert_eq!(statistics.total.correct, 1);
+        assert_eq!(statistics.classes["verb"].answered, 2);
+        assert_eq!(statistics.tones[&Tone::Nang].correct, 1);
+        assert!(!statistics.tones.contains_key(&Tone::Sac));
+        assert_eq!(
+            statistics.structures["VERB (verb_phrases.txt)"].accuracy(),
+            50.0
+        );
+        assert_eq!(statistics.words[&word.id()].answered, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_weakest_words() {
+        let history = vec![
+            answer(0, &[Item::Word("a".to_string())], Grade::Correct),
+            answer(0, &[Item::Word("b".to_string())], Grade::Wrong),
+        ];
+        let statistics = Statistics::new(&history);
+        let weakest = statistics.weakest_words();
+        assert_eq!(weakest[0].0, "b");
+        assert_eq!(weakest[1].0, "a");
+    }
+
+    #[test]
+    fn test_daily() {
+        let history = vec![
+            answer(0, &[], Grade::Correct),
+            answer(2 * DAY, &[], Grade::Wrong),
+            answer(2 * DAY + 1, &[], Grade::Correct),
+        ];
+
+        let days = daily(&history, 2 * DAY + 2, 2);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].answered, 0);
+        assert_eq!(days[1].answered, 2);
+        assert_eq!(days[1].correct, 1);
+    }
+}